@@ -1,13 +1,14 @@
 //! This is the implementation crate for `display-as-template`.
 
 extern crate proc_macro;
-// extern crate syn;
+extern crate syn;
 #[macro_use]
 extern crate quote;
 extern crate glob;
 extern crate proc_macro2;
 
 use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
+use quote::ToTokens;
 use std::fmt::Write;
 use std::fs::File;
 use std::io::Read;
@@ -22,7 +23,7 @@ fn find_template_file(path: &str) -> PathBuf {
     let paths: Vec<_> = sourcedirs.into_iter()
         .filter(|d| d.join(path).exists())
         .collect();
-    if paths.len() == 0 {
+    if paths.is_empty() {
         panic!("No template file named {:?} exists.", path);
     } else if paths.len() > 1 {
         panic!(r"Multiple files named {:?} exist.  Eventually display-as will
@@ -42,7 +43,7 @@ fn is_str(x: &TokenTree) -> bool {
     match x {
         TokenTree::Literal(_) => {
             let s = x.to_string();
-            s.len() > 0 && s.contains("\"") && s.chars().next() != Some('b')
+            !s.is_empty() && s.contains("\"") && !s.starts_with('b')
         }
         _ => false,
     }
@@ -77,7 +78,7 @@ pub fn format_as(input: TokenStream) -> TokenStream {
         if &comma.to_string() != "," {
             panic!(
                 "format_as! needs a Format followed by a comma, not {}",
-                comma.to_string()
+                comma
             );
         }
     } else {
@@ -120,7 +121,7 @@ pub fn write_as(input: TokenStream) -> TokenStream {
         if &comma.to_string() != "," {
             panic!(
                 "write_as! needs a Format followed by a comma, not {}",
-                comma.to_string()
+                comma
             );
         }
     } else {
@@ -128,14 +129,14 @@ pub fn write_as(input: TokenStream) -> TokenStream {
     }
 
     let mut writer: Vec<TokenTree> = Vec::new();
-    while let Some(tok) = tokens.next() {
+    for tok in tokens.by_ref() {
         if &tok.to_string() == "," {
             break;
         } else {
             writer.push(tok);
         }
     }
-    if writer.len() == 0 {
+    if writer.is_empty() {
         panic!("write_as! needs a Writer as its second argument followed by comma.")
     }
     let writer = proc_to_two(writer.into_iter().collect());
@@ -184,7 +185,13 @@ fn expr_toks_to_stmt(
             .take(13)
             .collect();
         proc_macro2::Ident::new(
-            &format!("ToDisplayAs{}xxx{}", format, rand_chars),
+            &format!(
+                "ToDisplayAs{}xxx{}",
+                format
+                    .to_string()
+                    .replace(|c: char| !c.is_ascii_alphanumeric(), ""),
+                rand_chars
+            ),
             proc_macro2::Span::call_site(),
         )
     };
@@ -204,7 +211,7 @@ fn expr_toks_to_stmt(
             }
         })
         .into_iter()
-    } else if expr.len() > 0 {
+    } else if !expr.is_empty() {
         let expr = proc_to_two(expr.drain(..).collect());
         let format = format.clone();
         two_to_proc(quote! {
@@ -229,13 +236,13 @@ fn expr_toks_to_conditional(expr: &mut Vec<TokenTree>) -> TokenStream {
 
 fn read_template_file(dirname: &Path, pathname: &str,
                       left_delim: &str, right_delim: &str) -> TokenStream {
-    let path = dirname.join(&pathname);
+    let path = dirname.join(pathname);
     if let Ok(mut f) = File::open(&path) {
         let mut contents = String::new();
         f.read_to_string(&mut contents)
             .expect("something went wrong reading the file");
         let raw_template_len = contents.len();
-        let pounds: String = if left_delim == "" {
+        let pounds: String = if left_delim.is_empty() {
             count_pounds(&contents).to_string()
         } else {
             let mut pounds = count_pounds(&contents).to_string();
@@ -253,7 +260,7 @@ fn read_template_file(dirname: &Path, pathname: &str,
         template
             .write_str("  ({ assert_eq!(include_str!(\"")
             .unwrap();
-        template.write_str(&pathname).unwrap();
+        template.write_str(pathname).unwrap();
         write!(template, "\").len(), {}); \"\"}}); ", raw_template_len).unwrap();
         template.parse().expect("trouble parsing file")
     } else {
@@ -261,6 +268,7 @@ fn read_template_file(dirname: &Path, pathname: &str,
     }
 }
 
+#[allow(clippy::if_same_then_else)]
 fn template_to_statements(
     dir: &Path,
     format: &proc_macro2::TokenStream,
@@ -296,7 +304,7 @@ fn template_to_statements(
                     // We are doing an assignment to a template
                     // thingy, so let's create a DisplayAs thingy
                     // rather than adding the stuff right now.
-                    toks.extend(expr_toks_to_conditional(&mut next_expr).into_iter());
+                    toks.extend(expr_toks_to_conditional(&mut next_expr));
                     let actions = proc_to_two(template_to_statements(dir, format, g.stream(),
                                                                      left_delim, right_delim));
                     toks.extend(
@@ -311,11 +319,10 @@ fn template_to_statements(
                             //     { #actions };
                             //     Ok(())
                             // }
-                        })
-                        .into_iter(),
+                        }),
                     );
                 } else if next_expr_len > 0 && &next_expr[0].to_string() == "match" {
-                    toks.extend(expr_toks_to_conditional(&mut next_expr).into_iter());
+                    toks.extend(expr_toks_to_conditional(&mut next_expr));
                     let mut interior_toks: Vec<TokenTree> = Vec::new();
                     for x in g.stream() {
                         if let TokenTree::Group(g) = x.clone() {
@@ -335,7 +342,7 @@ fn template_to_statements(
                     toks.push(TokenTree::Group(Group::new(Delimiter::Brace,
                                                           interior_toks.into_iter().collect())));
                 } else {
-                    toks.extend(expr_toks_to_conditional(&mut next_expr).into_iter());
+                    toks.extend(expr_toks_to_conditional(&mut next_expr));
                     toks.push(TokenTree::Group(Group::new(
                         Delimiter::Brace,
                         template_to_statements(dir, format, g.stream(),
@@ -359,19 +366,19 @@ fn template_to_statements(
                 let templ = read_template_file(dir, &filename, left_delim, right_delim);
                 let statements = template_to_statements(dir, format, templ,
                                                         left_delim, right_delim);
-                next_expr.extend(statements.into_iter());
-                next_expr.extend(to_tokens(";").into_iter());
-                toks.extend(expr_toks_to_conditional(&mut next_expr).into_iter());
+                next_expr.extend(statements);
+                next_expr.extend(to_tokens(";"));
+                toks.extend(expr_toks_to_conditional(&mut next_expr));
                 toks.push(t);
             } else {
                 next_expr.push(t);
             }
         } else if t.to_string() == ";" {
-            toks.extend(expr_toks_to_conditional(&mut next_expr).into_iter());
+            toks.extend(expr_toks_to_conditional(&mut next_expr));
             toks.push(t);
         } else if is_str(&t) {
             // First print the previous expression...
-            toks.extend(expr_toks_to_stmt(&format, &mut next_expr));
+            toks.extend(expr_toks_to_stmt(format, &mut next_expr));
             // Now we print this str...
             toks.extend(to_tokens("__f.write_str"));
             toks.push(TokenTree::Group(Group::new(
@@ -384,7 +391,7 @@ fn template_to_statements(
         }
     }
     // Now print the final expression...
-    toks.extend(expr_toks_to_stmt(&format, &mut next_expr));
+    toks.extend(expr_toks_to_stmt(format, &mut next_expr));
     TokenTree::Group(Group::new(Delimiter::Brace, toks.into_iter().collect())).into()
 }
 
@@ -435,7 +442,19 @@ pub fn with_template(input: TokenStream, my_impl: TokenStream) -> TokenStream {
     let mut my_format: proc_macro2::TokenStream = quote!();
     for i in 0..impl_toks.len() - 2 {
         if impl_toks[i].to_string() == "DisplayAs" && impl_toks[i + 1].to_string() == "<" {
-            my_format = proc_to_two(impl_toks[i + 2].clone().into());
+            // The format may be a path such as `display_as::HTML`, so
+            // take everything up to the matching `>`.
+            let mut depth = 0;
+            let format = impl_toks[i + 2..].iter().take_while(|t| {
+                match t.to_string().as_str() {
+                    "<" => depth += 1,
+                    ">" if depth == 0 => return false,
+                    ">" => depth -= 1,
+                    _ => (),
+                }
+                true
+            });
+            my_format = proc_to_two(format.cloned().collect());
             break;
         }
     }
@@ -481,7 +500,7 @@ pub fn with_template(input: TokenStream, my_impl: TokenStream) -> TokenStream {
         }
     };
     let mut new_impl: Vec<TokenTree> = Vec::new();
-    new_impl.extend(impl_toks.into_iter());
+    new_impl.extend(impl_toks);
     new_impl.extend(
         two_to_proc(quote! {
             {
@@ -489,8 +508,7 @@ pub fn with_template(input: TokenStream, my_impl: TokenStream) -> TokenStream {
                     #out
                 }
             }
-        })
-        .into_iter(),
+        }),
     );
     let new_impl = new_impl.into_iter().collect();
 
@@ -500,8 +518,63 @@ pub fn with_template(input: TokenStream, my_impl: TokenStream) -> TokenStream {
 
 /// Like [macro@with_template], but also generate any web responder
 /// implementations that are handled via feature flags.
+///
+/// The type itself then implements the responder traits of each web
+/// framework feature enabled in `display-as`, so a handler may return
+/// it directly, with a `Content-Type` given by the format.
+///
+/// A type can only be a response in one format, so use
+/// `with_response_template` on just one `DisplayAs` impl of each type,
+/// and [macro@with_template] on the others; using it on two impls for
+/// the same type is an error.  To choose a format for each request,
+/// return a `Negotiate` instead.  If the format is a type parameter,
+/// as in `impl<F: Format> DisplayAs<F> for T`, there is no one format
+/// to respond with, so this is just [macro@with_template].
 #[proc_macro_attribute]
 pub fn with_response_template(input: TokenStream, my_impl: TokenStream) -> TokenStream {
-    let displayas_impl = with_template(input, my_impl.clone());
-    displayas_impl
+    let item: syn::ItemImpl = match syn::parse(my_impl) {
+        Ok(item) => item,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let format = item
+        .trait_
+        .as_ref()
+        .and_then(|(_, path, _)| path.segments.last())
+        .filter(|segment| segment.ident == "DisplayAs")
+        .and_then(|segment| match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|a| match a {
+                syn::GenericArgument::Type(format) => Some(format.clone()),
+                _ => None,
+            }),
+            _ => None,
+        });
+    let format = match format {
+        Some(format) => format,
+        None => {
+            return syn::Error::new_spanned(
+                &item.self_ty,
+                "with_response_template can only be applied to an impl of DisplayAs<Format>",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+    let is_generic = match &format {
+        syn::Type::Path(p) if p.qself.is_none() => item
+            .generics
+            .type_params()
+            .any(|param| p.path.is_ident(&param.ident)),
+        _ => false,
+    };
+
+    let generics = item.generics.params.clone();
+    let self_type = item.self_ty.clone();
+    let where_clause = item.generics.where_clause.clone();
+    let mut out = with_template(input, item.into_token_stream().into());
+    if !is_generic {
+        out.extend(two_to_proc(quote! {
+            ::display_as::impl_responders!([#generics] [#format] [#self_type] [#where_clause]);
+        }));
+    }
+    out
 }
@@ -0,0 +1,139 @@
+//! [Format] as a field of comma-separated values
+
+use super::*;
+use crate::float::Localized;
+
+/// [Format] as a field of comma-separated values.
+///
+/// Text is quoted as described in RFC 4180 if it contains a comma, a
+/// double quote or a line break, so that each value displays as a
+/// single valid field.  A [`Table`] writes its cells as the fields of
+/// its records.
+///
+/// ```
+/// use display_as::{CSV, format_as};
+/// use display_as::float::{Locale, Localized};
+/// let text = "a, \"b\"";
+/// assert_eq!(&format_as!(CSV, text).into_string(), "\"a, \"\"b\"\"\"");
+/// assert_eq!(&format_as!(CSV, Localized::new(1234, Locale::EN)).into_string(),
+///            "\"1,234\"");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CSV;
+impl Format for CSV {
+    fn escape(f: &mut Formatter, s: &str) -> Result<(), Error> {
+        write_field(f, s)
+    }
+    /// The MIME type for CSV is [mime::TEXT_CSV_UTF_8].
+    fn mime() -> mime::Mime {
+        mime::TEXT_CSV_UTF_8
+    }
+    fn this_format() -> Self {
+        CSV
+    }
+}
+
+display_integers_as!(CSV);
+display_bools_and_chars_as!(CSV);
+display_floats_as!(CSV, "e", "", 1, None);
+display_measurements_as!(CSV, "e", "", 1, " ± ", " ", "");
+display_humanized_as!(CSV, " ", "", " ", crate::float::PrefixStyle::Unicode);
+
+/// [CSV] without any quoting, in which a [Localized] number is
+/// written before it is quoted as a whole, since its digit separators
+/// may be commas.
+#[doc(hidden)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Unquoted;
+impl Format for Unquoted {
+    fn escape(f: &mut Formatter, s: &str) -> Result<(), Error> {
+        f.write_str(s)
+    }
+    fn mime() -> mime::Mime {
+        mime::TEXT_CSV_UTF_8
+    }
+    fn this_format() -> Self {
+        Unquoted
+    }
+}
+
+display_localized_as!(Unquoted, "e", "", 1, None);
+
+impl<T> DisplayAs<CSV> for Localized<T>
+where
+    Localized<T>: DisplayAs<Unquoted>,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write_field(f, &format!("{}", As::<Unquoted, _>::from(self)))
+    }
+}
+
+/// Write `field`, quoted as described in RFC 4180 if it contains a
+/// comma, a double quote or a line break.
+pub(crate) fn write_field(f: &mut Formatter, field: &str) -> Result<(), Error> {
+    if field.contains(&[',', '"', '\r', '\n'][..]) {
+        f.write_str("\"")?;
+        f.write_str(&field.replace('"', "\"\""))?;
+        f.write_str("\"")
+    } else {
+        f.write_str(field)
+    }
+}
+
+/// Is `field` already a valid field, either with no comma, double
+/// quote or line break, or quoted as described in RFC 4180?
+pub(crate) fn is_field(field: &str) -> bool {
+    let quoted = field.strip_prefix('"').and_then(|f| f.strip_suffix('"'));
+    match quoted {
+        Some(inner) => !inner.replace("\"\"", "").contains('"'),
+        None => !field.contains(&[',', '"', '\r', '\n'][..]),
+    }
+}
+
+#[test]
+fn escaping() {
+    use crate::float::Locale;
+    assert_eq!(
+        &format_as!(CSV, ("a, \"b\"")).into_string(),
+        "\"a, \"\"b\"\"\""
+    );
+    assert_eq!(&format_as!(CSV, "plain").into_string(), "plain");
+    assert_eq!(
+        &format_as!(CSV, Localized::new(1234567, Locale::EN)).into_string(),
+        "\"1,234,567\""
+    );
+    assert_eq!(
+        &format_as!(CSV, Localized::new(1.5, Locale::DE)).into_string(),
+        "\"1,5\""
+    );
+    assert_eq!(
+        &format_as!(CSV, Localized::new(2.5, Locale::PLAIN)).into_string(),
+        "2.5"
+    );
+}
+#[test]
+fn floats() {
+    assert_eq!(&format_as!(CSV, 3.0).into_string(), "3");
+    assert_eq!(&format_as!(CSV, 3e5).into_string(), "3e5");
+    assert_eq!(&format_as!(CSV, 1.5e-7).into_string(), "1.5e-7");
+}
+#[test]
+fn fields() {
+    let field = |s: &str| {
+        format!(
+            "{}",
+            display_closure_as(CSV, |f| write_field(f, s)).display()
+        )
+    };
+    assert_eq!(field("plain"), "plain");
+    assert_eq!(field(""), "");
+    assert_eq!(field("a,b"), "\"a,b\"");
+    assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(field("two\nlines"), "\"two\nlines\"");
+    assert_eq!(field("cr\r"), "\"cr\r\"");
+    assert!(is_field("plain"));
+    assert!(is_field("\"a,\"\"b\"\"\""));
+    assert!(!is_field("a,b"));
+    assert!(!is_field("\"a\"b\""));
+    assert!(!is_field("\""));
+}
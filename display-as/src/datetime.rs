@@ -0,0 +1,409 @@
+//! Dates and times, behind the `chrono` and `time` feature flags.
+//!
+//! Each date or time type from those crates is displayed in
+//! ISO 8601 in every [Format], and in [HTML] within a `<time>`
+//! element with a machine-readable `datetime` attribute.  To choose a
+//! different style, wrap it in a [Timestamp].
+//!
+//! ```
+//! # #[cfg(feature = "chrono")] {
+//! use chrono::{TimeZone, Utc};
+//! use display_as::{HTML, UTF8, format_as};
+//! use display_as::datetime::Timestamp;
+//! let then = Utc.with_ymd_and_hms(2021, 3, 14, 15, 9, 26).unwrap();
+//! let now = Utc.with_ymd_and_hms(2021, 3, 14, 15, 12, 26).unwrap();
+//! assert_eq!(&format_as!(UTF8, then).into_string(), "2021-03-14T15:09:26Z");
+//! assert_eq!(&format_as!(HTML, then.date_naive()).into_string(),
+//!            "<time datetime=\"2021-03-14\">2021-03-14</time>");
+//! assert_eq!(&format_as!(UTF8, Timestamp::relative(then, now)).into_string(),
+//!            "3 minutes ago");
+//! assert_eq!(&format_as!(HTML, Timestamp::pattern(then, "%B %-d, %Y")).into_string(),
+//!            "<time datetime=\"2021-03-14T15:09:26Z\">March 14, 2021</time>");
+//! # }
+//! ```
+
+use super::*;
+
+/// A date, time or both, which can be displayed by [Timestamp].
+///
+/// This is implemented for the date and time types of `chrono` and
+/// `time`, according to which feature flags are enabled.
+pub trait Temporal {
+    /// Write this in ISO 8601 format.
+    fn iso8601(&self, out: &mut String) -> Result<(), Error>;
+    /// Write this in RFC 2822 format, as used in email.  Types lacking
+    /// a date, time or offset fall back to ISO 8601.
+    fn rfc2822(&self, out: &mut String) -> Result<(), Error> {
+        self.iso8601(out)
+    }
+    /// Write this according to `pattern`, using the syntax of the
+    /// crate that defines the type (e.g. `%Y-%m-%d` for `chrono`, or
+    /// `[year]-[month]-[day]` for `time`).
+    ///
+    /// Patterns often come from users, so a pattern that is invalid
+    /// or that needs something this type lacks, such as an offset,
+    /// writes a message like `invalid date pattern "%Q"` rather than
+    /// failing.
+    fn pattern(&self, out: &mut String, pattern: &str) -> Result<(), Error>;
+    /// The number of whole seconds between `earlier` and this.
+    fn seconds_since(&self, earlier: &Self) -> i64;
+}
+
+/// A date or time displayed in a particular style.
+///
+/// In [HTML] it is wrapped in a `<time>` element, whose `datetime`
+/// attribute holds the ISO 8601 representation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timestamp<'a, T> {
+    value: T,
+    style: Style<'a, T>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Style<'a, T> {
+    Iso8601,
+    Rfc2822,
+    Relative(T),
+    Pattern(&'a str),
+}
+
+impl<'a, T: Temporal> Timestamp<'a, T> {
+    /// Display `value` in ISO 8601 format, as in `2021-03-14T15:09:26Z`.
+    pub fn iso8601(value: T) -> Self {
+        Timestamp {
+            value,
+            style: Style::Iso8601,
+        }
+    }
+    /// Display `value` in RFC 2822 format, as in
+    /// `Sun, 14 Mar 2021 15:09:26 +0000`.
+    pub fn rfc2822(value: T) -> Self {
+        Timestamp {
+            value,
+            style: Style::Rfc2822,
+        }
+    }
+    /// Display `value` relative to `now`, as in `3 minutes ago` or
+    /// `in 2 days`.
+    pub fn relative(value: T, now: T) -> Self {
+        Timestamp {
+            value,
+            style: Style::Relative(now),
+        }
+    }
+    /// Display `value` according to `pattern`, as described for
+    /// [Temporal::pattern], which writes a message in place of the
+    /// date if the pattern is invalid.
+    pub fn pattern(value: T, pattern: &'a str) -> Self {
+        Timestamp {
+            value,
+            style: Style::Pattern(pattern),
+        }
+    }
+
+    fn text(&self) -> Result<String, Error> {
+        let mut out = String::new();
+        match &self.style {
+            Style::Iso8601 => self.value.iso8601(&mut out)?,
+            Style::Rfc2822 => self.value.rfc2822(&mut out)?,
+            Style::Relative(now) => out = relative(self.value.seconds_since(now)),
+            Style::Pattern(pattern) => self.value.pattern(&mut out, pattern)?,
+        }
+        Ok(out)
+    }
+
+    /// Format this timestamp, writing `before` and `after` around the
+    /// (escaped) text.
+    pub fn fmt_with<F: Format>(
+        &self,
+        f: &mut Formatter,
+        before: &str,
+        after: &str,
+    ) -> Result<(), Error> {
+        f.write_str(before)?;
+        F::escape(f, &self.text()?)?;
+        f.write_str(after)
+    }
+}
+
+fn relative(seconds: i64) -> String {
+    let magnitude = seconds.abs();
+    let (count, unit) = if magnitude < 60 {
+        (magnitude, "second")
+    } else if magnitude < 60 * 60 {
+        (magnitude / 60, "minute")
+    } else if magnitude < 24 * 60 * 60 {
+        (magnitude / (60 * 60), "hour")
+    } else if magnitude < 30 * 24 * 60 * 60 {
+        (magnitude / (24 * 60 * 60), "day")
+    } else if magnitude < 365 * 24 * 60 * 60 {
+        (magnitude / (30 * 24 * 60 * 60), "month")
+    } else {
+        (magnitude / (365 * 24 * 60 * 60), "year")
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    if seconds == 0 {
+        "just now".to_string()
+    } else if seconds < 0 {
+        format!("{} {}{} ago", count, unit, plural)
+    } else {
+        format!("in {} {}{}", count, unit, plural)
+    }
+}
+
+impl<'a, T: Temporal> DisplayAs<HTML> for Timestamp<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let mut iso = String::new();
+        self.value.iso8601(&mut iso)?;
+        f.write_str("<time datetime=\"")?;
+        HTML::escape(f, &iso)?;
+        self.fmt_with::<HTML>(f, "\">", "</time>")
+    }
+}
+
+macro_rules! display_timestamps_as {
+    ($format:ty, $before:expr, $after:expr) => {
+        impl<'a, T: Temporal> DisplayAs<$format> for Timestamp<'a, T> {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                self.fmt_with::<$format>(f, $before, $after)
+            }
+        }
+    };
+}
+display_timestamps_as!(CSV, "", "");
+display_timestamps_as!(LaTeX, "", "");
+display_timestamps_as!(Markdown, "", "");
+display_timestamps_as!(Math, r"\text{", "}");
+display_timestamps_as!(Rust, "", "");
+display_timestamps_as!(URL, "", "");
+display_timestamps_as!(UTF8, "", "");
+
+macro_rules! display_temporal_as {
+    ($($type:ty),*) => {
+        $(
+            impl<F: Format> DisplayAs<F> for $type
+            where
+                Timestamp<'static, $type>: DisplayAs<F>,
+            {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    Timestamp::iso8601(self.clone()).fmt(f)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, TimeZone};
+    use std::fmt::Write;
+
+    /// Write `formatted`, or a message if `pattern` is invalid, which
+    /// `chrono` reports only by failing to display it.
+    fn write_pattern(
+        out: &mut String,
+        pattern: &str,
+        formatted: impl Display,
+    ) -> Result<(), Error> {
+        let mut text = String::new();
+        match write!(text, "{}", formatted) {
+            Ok(()) => out.push_str(&text),
+            Err(Error) => write!(out, "invalid date pattern {:?}", pattern)?,
+        }
+        Ok(())
+    }
+
+    impl<Tz: TimeZone> Temporal for DateTime<Tz>
+    where
+        Tz::Offset: Display,
+    {
+        fn iso8601(&self, out: &mut String) -> Result<(), Error> {
+            out.write_str(&self.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+        fn rfc2822(&self, out: &mut String) -> Result<(), Error> {
+            out.write_str(&self.to_rfc2822())
+        }
+        fn pattern(&self, out: &mut String, pattern: &str) -> Result<(), Error> {
+            write_pattern(out, pattern, self.format(pattern))
+        }
+        fn seconds_since(&self, earlier: &Self) -> i64 {
+            self.clone()
+                .signed_duration_since(earlier.clone())
+                .num_seconds()
+        }
+    }
+    impl<F: Format, Tz: TimeZone> DisplayAs<F> for DateTime<Tz>
+    where
+        Tz::Offset: Display,
+        Timestamp<'static, DateTime<Tz>>: DisplayAs<F>,
+    {
+        fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+            Timestamp::iso8601(self.clone()).fmt(f)
+        }
+    }
+
+    macro_rules! naive_temporal {
+        ($type:ty, $iso:expr) => {
+            impl Temporal for $type {
+                fn iso8601(&self, out: &mut String) -> Result<(), Error> {
+                    write!(out, "{}", self.format($iso))
+                }
+                fn pattern(&self, out: &mut String, pattern: &str) -> Result<(), Error> {
+                    write_pattern(out, pattern, self.format(pattern))
+                }
+                fn seconds_since(&self, earlier: &Self) -> i64 {
+                    self.signed_duration_since(*earlier).num_seconds()
+                }
+            }
+        };
+    }
+    naive_temporal!(NaiveDateTime, "%Y-%m-%dT%H:%M:%S%.f");
+    naive_temporal!(NaiveDate, "%Y-%m-%d");
+    naive_temporal!(NaiveTime, "%H:%M:%S%.f");
+    display_temporal_as!(NaiveDateTime, NaiveDate, NaiveTime);
+
+    #[test]
+    fn chrono() {
+        use chrono::{FixedOffset, Utc};
+        let t = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2021, 3, 14, 15, 9, 26)
+            .unwrap();
+        assert_eq!(
+            &format_as!(UTF8, t).into_string(),
+            "2021-03-14T15:09:26+01:00"
+        );
+        assert_eq!(
+            &format_as!(UTF8, Timestamp::rfc2822(t)).into_string(),
+            "Sun, 14 Mar 2021 15:09:26 +0100"
+        );
+        assert_eq!(
+            &format_as!(Math, Timestamp::iso8601(t)).into_string(),
+            r"\text{2021-03-14T15:09:26+01:00}"
+        );
+        assert_eq!(
+            &format_as!(LaTeX, Timestamp::pattern(t, "%d%% of %B")).into_string(),
+            r"14\% of March"
+        );
+        assert_eq!(
+            &format_as!(UTF8, Timestamp::pattern(t, "%Q %Y")).into_string(),
+            r#"invalid date pattern "%Q %Y""#
+        );
+        assert_eq!(
+            &format_as!(HTML, Timestamp::pattern(t, "<%Q>")).into_string(),
+            "<time datetime=\"2021-03-14T15:09:26+01:00\">invalid date pattern &quot;&lt;%Q&gt;&quot;</time>"
+        );
+        let date = t.with_timezone(&Utc).date_naive();
+        assert_eq!(&format_as!(LaTeX, date).into_string(), "2021-03-14");
+        assert_eq!(
+            &format_as!(HTML, Timestamp::rfc2822(date)).into_string(),
+            "<time datetime=\"2021-03-14\">2021-03-14</time>"
+        );
+        let later = date.succ_opt().unwrap().succ_opt().unwrap();
+        assert_eq!(
+            &format_as!(UTF8, Timestamp::relative(later, date)).into_string(),
+            "in 2 days"
+        );
+        let noon = NaiveTime::from_hms_milli_opt(12, 0, 0, 500).unwrap();
+        assert_eq!(&format_as!(UTF8, noon).into_string(), "12:00:00.500");
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::*;
+    use std::fmt::Write;
+    use time::format_description::well_known::{Rfc2822, Rfc3339};
+    use time::format_description::BorrowedFormatItem;
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+    fn pattern(
+        out: &mut String,
+        pattern: &str,
+        format: impl FnOnce(&[BorrowedFormatItem]) -> Result<String, time::error::Format>,
+    ) -> Result<(), Error> {
+        // The version 1 syntax, which is all that older versions of
+        // `time` support.
+        #[allow(deprecated)]
+        let text = match time::format_description::parse(pattern) {
+            Ok(description) => format(&description).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match text {
+            Ok(text) => out.write_str(&text),
+            Err(e) => write!(out, "invalid date pattern {:?}: {}", pattern, e),
+        }
+    }
+
+    impl Temporal for OffsetDateTime {
+        fn iso8601(&self, out: &mut String) -> Result<(), Error> {
+            out.write_str(&self.format(&Rfc3339).map_err(|_| Error)?)
+        }
+        fn rfc2822(&self, out: &mut String) -> Result<(), Error> {
+            out.write_str(&self.format(&Rfc2822).map_err(|_| Error)?)
+        }
+        fn pattern(&self, out: &mut String, p: &str) -> Result<(), Error> {
+            pattern(out, p, |d| self.format(d))
+        }
+        fn seconds_since(&self, earlier: &Self) -> i64 {
+            (*self - *earlier).whole_seconds()
+        }
+    }
+
+    macro_rules! primitive_temporal {
+        ($type:ty, $iso:expr, $subsecond:expr) => {
+            impl Temporal for $type {
+                fn iso8601(&self, out: &mut String) -> Result<(), Error> {
+                    if $subsecond(self) {
+                        self.pattern(out, concat!($iso, ".[subsecond]"))
+                    } else {
+                        self.pattern(out, $iso)
+                    }
+                }
+                fn pattern(&self, out: &mut String, p: &str) -> Result<(), Error> {
+                    pattern(out, p, |d| self.format(d))
+                }
+                fn seconds_since(&self, earlier: &Self) -> i64 {
+                    (*self - *earlier).whole_seconds()
+                }
+            }
+        };
+    }
+    primitive_temporal!(
+        PrimitiveDateTime,
+        "[year]-[month]-[day]T[hour]:[minute]:[second]",
+        |t: &PrimitiveDateTime| t.nanosecond() != 0
+    );
+    primitive_temporal!(Date, "[year]-[month]-[day]", |_| false);
+    primitive_temporal!(Time, "[hour]:[minute]:[second]", |t: &Time| t.nanosecond()
+        != 0);
+    display_temporal_as!(OffsetDateTime, PrimitiveDateTime, Date, Time);
+
+    #[test]
+    fn time() {
+        let t = OffsetDateTime::from_unix_timestamp(1615734566).unwrap();
+        assert_eq!(
+            &format_as!(HTML, t).into_string(),
+            "<time datetime=\"2021-03-14T15:09:26Z\">2021-03-14T15:09:26Z</time>"
+        );
+        assert_eq!(
+            &format_as!(UTF8, Timestamp::rfc2822(t)).into_string(),
+            "Sun, 14 Mar 2021 15:09:26 +0000"
+        );
+        assert_eq!(
+            &format_as!(HTML, Timestamp::pattern(t, "[day] [month repr:short]")).into_string(),
+            "<time datetime=\"2021-03-14T15:09:26Z\">14 Mar</time>"
+        );
+        let earlier = t - time::Duration::hours(5);
+        assert_eq!(
+            &format_as!(UTF8, Timestamp::relative(earlier, t)).into_string(),
+            "5 hours ago"
+        );
+        assert_eq!(&format_as!(UTF8, t.date()).into_string(), "2021-03-14");
+        assert_eq!(&format_as!(UTF8, t.time()).into_string(), "15:09:26");
+        let bad = |pattern| format_as!(UTF8, Timestamp::pattern(t.date(), pattern)).into_string();
+        assert!(bad("[year").starts_with(r#"invalid date pattern "[year": "#));
+        assert!(bad("[hour]").starts_with(r#"invalid date pattern "[hour]": "#));
+    }
+}
@@ -0,0 +1,304 @@
+//! Typed math expressions, which can be written once and displayed as
+//! [Math], [MathML] or plain [UTF8].
+//!
+//! ```
+//! use display_as::{Math, MathML, UTF8, format_as};
+//! use display_as::expr::{frac, pow, sqrt, sub, sum, var};
+//! let x = frac(sqrt(pow(var("x"), 2)), sub(var("a"), 1));
+//! assert_eq!(&format_as!(Math, x).into_string(), r"\frac{\sqrt{{x}^{2}}}{{a}_{1}}");
+//! assert_eq!(&format_as!(UTF8, x).into_string(), "(√x²)/a₁");
+//! assert_eq!(&format_as!(MathML, x).into_string(),
+//!            "<mfrac><mrow><msqrt><msup><mrow><mi>x</mi></mrow><mrow><mn>2</mn></mrow></msup>\
+//!             </msqrt></mrow><mrow><msub><mrow><mi>a</mi></mrow><mrow><mn>1</mn></mrow></msub>\
+//!             </mrow></mfrac>");
+//! let total = sum("i=1", "n", sub(var("x"), var("i")));
+//! assert_eq!(&format_as!(Math, total).into_string(), r"\sum_{i=1}^{n} {x}_{i}");
+//! assert_eq!(&format_as!(UTF8, total).into_string(), "∑ᵢ₌₁ⁿ xᵢ");
+//! ```
+//!
+//! In [MathML] a string is only escaped, so variables should be
+//! wrapped in [var] to put them in an `<mi>` element.
+
+use crate::{As, DisplayAs, Math, MathML, UTF8};
+use std::fmt::{Error, Formatter};
+
+/// A variable, which is an `<mi>` in [MathML].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Var<T>(pub T);
+/// A fraction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Frac<A, B>(pub A, pub B);
+/// A square root.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Sqrt<T>(pub T);
+/// A base raised to an exponent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pow<T, N>(pub T, pub N);
+/// A base with a subscript.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Sub<T, I>(pub T, pub I);
+/// A sum with limits below and above.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Sum<L, U, T> {
+    /// The limit below the sum, such as `i=1`
+    pub below: L,
+    /// The limit above the sum, such as `n`
+    pub above: U,
+    /// The terms being summed
+    pub body: T,
+}
+
+/// A variable `x`.
+pub fn var<T>(x: T) -> Var<T> {
+    Var(x)
+}
+/// The fraction `a/b`.
+pub fn frac<A, B>(a: A, b: B) -> Frac<A, B> {
+    Frac(a, b)
+}
+/// The square root of `x`.
+pub fn sqrt<T>(x: T) -> Sqrt<T> {
+    Sqrt(x)
+}
+/// `x` to the power `n`.
+pub fn pow<T, N>(x: T, n: N) -> Pow<T, N> {
+    Pow(x, n)
+}
+/// `x` with subscript `i`.
+pub fn sub<T, I>(x: T, i: I) -> Sub<T, I> {
+    Sub(x, i)
+}
+/// The sum of `body` from `below` to `above`.
+pub fn sum<L, U, T>(below: L, above: U, body: T) -> Sum<L, U, T> {
+    Sum { below, above, body }
+}
+
+impl<T: DisplayAs<Math>> DisplayAs<Math> for Var<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        self.0.fmt(f)
+    }
+}
+impl<A: DisplayAs<Math>, B: DisplayAs<Math>> DisplayAs<Math> for Frac<A, B> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str(r"\frac{")?;
+        self.0.fmt(f)?;
+        f.write_str("}{")?;
+        self.1.fmt(f)?;
+        f.write_str("}")
+    }
+}
+impl<T: DisplayAs<Math>> DisplayAs<Math> for Sqrt<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str(r"\sqrt{")?;
+        self.0.fmt(f)?;
+        f.write_str("}")
+    }
+}
+impl<T: DisplayAs<Math>, N: DisplayAs<Math>> DisplayAs<Math> for Pow<T, N> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("{")?;
+        self.0.fmt(f)?;
+        f.write_str("}^{")?;
+        self.1.fmt(f)?;
+        f.write_str("}")
+    }
+}
+impl<T: DisplayAs<Math>, I: DisplayAs<Math>> DisplayAs<Math> for Sub<T, I> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("{")?;
+        self.0.fmt(f)?;
+        f.write_str("}_{")?;
+        self.1.fmt(f)?;
+        f.write_str("}")
+    }
+}
+impl<L: DisplayAs<Math>, U: DisplayAs<Math>, T: DisplayAs<Math>> DisplayAs<Math> for Sum<L, U, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str(r"\sum_{")?;
+        self.below.fmt(f)?;
+        f.write_str("}^{")?;
+        self.above.fmt(f)?;
+        f.write_str("} ")?;
+        self.body.fmt(f)
+    }
+}
+
+/// Write `tag` with each of `children` in an `<mrow>`.
+fn mathml_element(
+    f: &mut Formatter,
+    tag: &str,
+    children: &[&dyn DisplayAs<MathML>],
+) -> Result<(), Error> {
+    write!(f, "<{}>", tag)?;
+    for c in children {
+        f.write_str("<mrow>")?;
+        c.fmt(f)?;
+        f.write_str("</mrow>")?;
+    }
+    write!(f, "</{}>", tag)
+}
+
+impl<T: DisplayAs<MathML>> DisplayAs<MathML> for Var<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("<mi>")?;
+        self.0.fmt(f)?;
+        f.write_str("</mi>")
+    }
+}
+impl<A: DisplayAs<MathML>, B: DisplayAs<MathML>> DisplayAs<MathML> for Frac<A, B> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        mathml_element(f, "mfrac", &[&self.0, &self.1])
+    }
+}
+impl<T: DisplayAs<MathML>> DisplayAs<MathML> for Sqrt<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("<msqrt>")?;
+        self.0.fmt(f)?;
+        f.write_str("</msqrt>")
+    }
+}
+impl<T: DisplayAs<MathML>, N: DisplayAs<MathML>> DisplayAs<MathML> for Pow<T, N> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        mathml_element(f, "msup", &[&self.0, &self.1])
+    }
+}
+impl<T: DisplayAs<MathML>, I: DisplayAs<MathML>> DisplayAs<MathML> for Sub<T, I> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        mathml_element(f, "msub", &[&self.0, &self.1])
+    }
+}
+impl<L: DisplayAs<MathML>, U: DisplayAs<MathML>, T: DisplayAs<MathML>> DisplayAs<MathML>
+    for Sum<L, U, T>
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("<mrow><munderover><mo>∑</mo><mrow>")?;
+        self.below.fmt(f)?;
+        f.write_str("</mrow><mrow>")?;
+        self.above.fmt(f)?;
+        f.write_str("</mrow></munderover>")?;
+        self.body.fmt(f)?;
+        f.write_str("</mrow>")
+    }
+}
+
+const SUPERSCRIPTS: &str = "0⁰1¹2²3³4⁴5⁵6⁶7⁷8⁸9⁹+⁺-⁻−⁻=⁼(⁽)⁾nⁿiⁱ";
+const SUBSCRIPTS: &str = "0₀1₁2₂3₃4₄5₅6₆7₇8₈9₉+₊-₋−₋=₌(₍)₎aₐeₑoₒxₓhₕkₖlₗmₘnₙpₚsₛtₜiᵢjⱼrᵣuᵤvᵥ";
+
+/// Display `x` as [UTF8].
+fn utf8<T: DisplayAs<UTF8> + ?Sized>(x: &T) -> String {
+    format!("{}", As::<UTF8, T>::from(x))
+}
+
+/// Write `s`, in parentheses unless it is a single number or name.
+fn write_grouped(f: &mut Formatter, s: &str) -> Result<(), Error> {
+    let is_atom = s.chars().all(|c| c.is_alphanumeric() || c == '.');
+    if is_atom {
+        f.write_str(s)
+    } else {
+        write!(f, "({})", s)
+    }
+}
+
+/// Write `s` using the unicode script characters in `scripts`, or
+/// after `fallback` if some character has no such form.
+fn write_script(f: &mut Formatter, s: &str, scripts: &str, fallback: &str) -> Result<(), Error> {
+    let script = |c: char| {
+        let mut chars = scripts.chars();
+        while let Some(plain) = chars.next() {
+            let small = chars.next();
+            if plain == c {
+                return small;
+            }
+        }
+        None
+    };
+    match s.chars().map(script).collect::<Option<String>>() {
+        Some(small) => f.write_str(&small),
+        None => {
+            f.write_str(fallback)?;
+            write_grouped(f, s)
+        }
+    }
+}
+
+impl<T: DisplayAs<UTF8>> DisplayAs<UTF8> for Var<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        self.0.fmt(f)
+    }
+}
+impl<A: DisplayAs<UTF8>, B: DisplayAs<UTF8>> DisplayAs<UTF8> for Frac<A, B> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write_grouped(f, &utf8(&self.0))?;
+        f.write_str("/")?;
+        write_grouped(f, &utf8(&self.1))
+    }
+}
+impl<T: DisplayAs<UTF8>> DisplayAs<UTF8> for Sqrt<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("√")?;
+        write_grouped(f, &utf8(&self.0))
+    }
+}
+impl<T: DisplayAs<UTF8>, N: DisplayAs<UTF8>> DisplayAs<UTF8> for Pow<T, N> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write_grouped(f, &utf8(&self.0))?;
+        write_script(f, &utf8(&self.1), SUPERSCRIPTS, "^")
+    }
+}
+impl<T: DisplayAs<UTF8>, I: DisplayAs<UTF8>> DisplayAs<UTF8> for Sub<T, I> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write_grouped(f, &utf8(&self.0))?;
+        write_script(f, &utf8(&self.1), SUBSCRIPTS, "_")
+    }
+}
+impl<L: DisplayAs<UTF8>, U: DisplayAs<UTF8>, T: DisplayAs<UTF8>> DisplayAs<UTF8> for Sum<L, U, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("∑")?;
+        write_script(f, &utf8(&self.below), SUBSCRIPTS, "_")?;
+        write_script(f, &utf8(&self.above), SUPERSCRIPTS, "^")?;
+        f.write_str(" ")?;
+        self.body.fmt(f)
+    }
+}
+
+#[test]
+fn utf8_scripts() {
+    use crate::format_as;
+    assert_eq!(&format_as!(UTF8, pow("x", 10)).into_string(), "x¹⁰");
+    assert_eq!(&format_as!(UTF8, pow(-3, 2)).into_string(), "(-3)²");
+    assert_eq!(&format_as!(UTF8, pow("e", "i π")).into_string(), "e^(i π)");
+    assert_eq!(&format_as!(UTF8, sub("x", "n-1")).into_string(), "xₙ₋₁");
+    assert_eq!(
+        &format_as!(UTF8, sub("x", "final")).into_string(),
+        "x_final"
+    );
+    assert_eq!(&format_as!(UTF8, frac(1, 2.5)).into_string(), "1/2.5");
+    assert_eq!(&format_as!(UTF8, sqrt(frac(1, 2))).into_string(), "√(1/2)");
+}
+
+#[test]
+fn math() {
+    use crate::format_as;
+    assert_eq!(
+        &format_as!(Math, frac(1, pow(10.0, 2))).into_string(),
+        r"\frac{1}{{10}^{2}}"
+    );
+    assert_eq!(
+        &format_as!(Math, sub("x", "max_1")).into_string(),
+        r"{x}_{max\_1}"
+    );
+}
+
+#[test]
+fn mathml() {
+    use crate::format_as;
+    assert_eq!(
+        &format_as!(MathML, sqrt(var("y"))).into_string(),
+        "<msqrt><mi>y</mi></msqrt>"
+    );
+    assert_eq!(
+        &format_as!(MathML, sum(1, 3, var("k"))).into_string(),
+        "<mrow><munderover><mo>∑</mo><mrow><mn>1</mn></mrow><mrow><mn>3</mn></mrow>\
+         </munderover><mi>k</mi></mrow>"
+    );
+}
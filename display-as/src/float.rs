@@ -1,19 +1,36 @@
-//! Internal helper code required for [display_floats_as].
+//! Helper code for displaying floating point numbers, required for
+//! [display_floats_as].
 //!
 //! The standard library does nice exact conversions to decimal, but
 //! lacks a nice output format, so this module helps to do that.  **Do
-//! not use this code direcly, but instead call [display_floats_as]!**
+//! not use [Floating] direcly, but instead call [display_floats_as]!**
+//!
+//! This module also defines wrapper types [SigFigs] and [Decimals],
+//! which you can use in a template to display a float with a given
+//! precision, using the notation appropriate for each format.
+//! [Localized] displays an integer or float with the decimal mark
+//! and digit grouping of a [Locale].
+//!
+//! ```
+//! use display_as::{HTML, Math, format_as};
+//! use display_as::float::{Decimals, SigFigs};
+//! assert_eq!(&format_as!(HTML, SigFigs(123456., 3)).into_string(),
+//!            "1.23×10<sup>5</sup>");
+//! assert_eq!(&format_as!(Math, SigFigs(0.5, 3)).into_string(), "0.500");
+//! assert_eq!(&format_as!(HTML, Decimals(1.23456, 2)).into_string(), "1.23");
+//! ```
 
-use std::fmt::{Display, Error, Formatter};
+use crate::Format;
+use std::fmt::{Display, Error, Formatter, Write};
 use std::str::FromStr;
 
 /// This represents an f32 or f64 that has been converted to a string,
 /// but which we have not yet decided for certain how to represent
 /// (e.g. how many digits to show, or whether to use `e` or `E`
 /// notation).
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub enum Floating {
-    /// A normal floating point number
+    /// A nonzero finite number, which may be subnormal
     Normal {
         /// The exponent
         exponent: i16,
@@ -22,14 +39,33 @@ pub enum Floating {
         /// Is it negative?
         is_negative: bool,
     },
-    /// This is a NaN or an infinity
-    Abnormal(String),
+    /// Zero, which is displayed without any sign
+    Zero {
+        /// Is it negative zero?
+        negative: bool,
+        /// The number of zeros after the decimal point
+        decimals: usize,
+    },
+    /// An infinity, which each [Format] may display as it likes
+    Infinite {
+        /// Is it negative infinity?
+        negative: bool,
+    },
+    /// Not a number
+    NaN,
 }
 
 impl From<f64> for Floating {
     fn from(x: f64) -> Self {
-        if !x.is_normal() {
-            return Floating::Abnormal(format!("{}", x));
+        if x.is_nan() {
+            return Floating::NaN;
+        } else if x.is_infinite() {
+            return Floating::Infinite { negative: x < 0. };
+        } else if x == 0. {
+            return Floating::Zero {
+                negative: x.is_sign_negative(),
+                decimals: 0,
+            };
         }
         let is_negative = x < 0.;
         let x = if is_negative { -x } else { x };
@@ -54,8 +90,15 @@ impl From<f64> for Floating {
 }
 impl From<f32> for Floating {
     fn from(x: f32) -> Self {
-        if !x.is_normal() {
-            return Floating::Abnormal(format!("{}", x));
+        if x.is_nan() {
+            return Floating::NaN;
+        } else if x.is_infinite() {
+            return Floating::Infinite { negative: x < 0. };
+        } else if x == 0. {
+            return Floating::Zero {
+                negative: x.is_sign_negative(),
+                decimals: 0,
+            };
         }
         let is_negative = x < 0.;
         let x = if is_negative { -x } else { x };
@@ -100,11 +143,253 @@ fn to_floating() {
 }
 
 impl Floating {
+    /// Convert `x` with exactly `digits` significant figures, rounding
+    /// as needed.
+    pub fn with_sig_figs(x: f64, digits: usize) -> Self {
+        if !x.is_finite() {
+            return Floating::from(x);
+        }
+        let digits = std::cmp::max(digits, 1);
+        Floating::from_exp_str(&format!("{:.*e}", digits - 1, x))
+    }
+
+    /// Convert `x` with exactly `decimals` digits after the decimal
+    /// point, rounding as needed.
+    pub fn with_decimals(x: f64, decimals: usize) -> Self {
+        if !x.is_finite() {
+            return Floating::from(x);
+        }
+        let x = format!("{:.*}", decimals, x);
+        Floating::from_decimal_str(&x)
+    }
+
+    /// Convert a number written in decimal, such as `-12.50`, keeping
+    /// every digit (including trailing zeros) that it was written
+    /// with.
+    pub(crate) fn from_decimal_str(x: &str) -> Self {
+        let (is_negative, x) = match x.strip_prefix('-') {
+            Some(x) => (true, x),
+            None => (false, x),
+        };
+        let integer_digits = x.find('.').unwrap_or(x.len()) as i16;
+        let digits = x.replace('.', "");
+        let mantissa = digits.trim_start_matches('0');
+        if mantissa.is_empty() {
+            return Floating::zero(digits.len() - integer_digits as usize);
+        }
+        let leading_zeros = (digits.len() - mantissa.len()) as i16;
+        Floating::Normal {
+            exponent: integer_digits - 1 - leading_zeros,
+            mantissa: mantissa.to_string(),
+            is_negative,
+        }
+    }
+
+    /// Convert a number written in scientific notation, such as
+    /// `-1.250e3`, keeping every digit of its mantissa.
+    fn from_exp_str(x: &str) -> Self {
+        let (is_negative, x) = match x.strip_prefix('-') {
+            Some(x) => (true, x),
+            None => (false, x),
+        };
+        let mut parts = x.splitn(2, 'e');
+        let mantissa = parts
+            .next()
+            .expect("float repr should have mantissa")
+            .replace('.', "");
+        if mantissa.bytes().all(|b| b == b'0') {
+            return Floating::zero(mantissa.len() - 1);
+        }
+        let exponent = i16::from_str(parts.next().expect("float repr should have exponent"))
+            .expect("exponent should be integer");
+        Floating::Normal {
+            exponent,
+            mantissa,
+            is_negative,
+        }
+    }
+
+    /// The `f64` closest to this number.  This is exactly the `f64`
+    /// it was converted from, if it was.
+    fn to_f64(&self) -> f64 {
+        match self {
+            Floating::Normal {
+                exponent,
+                mantissa,
+                is_negative,
+            } => {
+                let sign = if *is_negative { "-" } else { "" };
+                f64::from_str(&format!("{}0.{}e{}", sign, mantissa, exponent + 1))
+                    .expect("digits should parse as a float")
+            }
+            Floating::Zero { negative: true, .. } => -0.0,
+            Floating::Zero { negative: false, .. } => 0.0,
+            Floating::Infinite { negative: true } => f64::NEG_INFINITY,
+            Floating::Infinite { negative: false } => f64::INFINITY,
+            Floating::NaN => f64::NAN,
+        }
+    }
+
+    fn zero(decimals: usize) -> Self {
+        Floating::Zero {
+            negative: false,
+            decimals,
+        }
+    }
+
+    /// Round to `digits` significant figures, padding with zeros if
+    /// needed.
+    fn rounded(&self, digits: i16) -> Self {
+        match self {
+            Floating::Normal {
+                exponent,
+                mantissa,
+                is_negative,
+            } => {
+                let mut exponent = *exponent;
+                if digits < 0 {
+                    return Floating::zero(0);
+                }
+                let digits = digits as usize;
+                let mut m: Vec<u8> = mantissa.bytes().collect();
+                if m.len() > digits {
+                    let round_up = m[digits] >= b'5';
+                    m.truncate(digits);
+                    if round_up {
+                        let mut i = digits;
+                        loop {
+                            if i == 0 {
+                                m.insert(0, b'1');
+                                exponent += 1;
+                                if m.len() > digits && digits > 0 {
+                                    m.pop();
+                                }
+                                break;
+                            }
+                            i -= 1;
+                            if m[i] == b'9' {
+                                m[i] = b'0';
+                            } else {
+                                m[i] += 1;
+                                break;
+                            }
+                        }
+                    }
+                }
+                if m.is_empty() {
+                    return Floating::zero(0);
+                }
+                while m.len() < digits {
+                    m.push(b'0');
+                }
+                Floating::Normal {
+                    exponent,
+                    mantissa: String::from_utf8(m).expect("digits are ascii"),
+                    is_negative: *is_negative,
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Round to the digit representing `10^last`, padding with zeros
+    /// if needed.
+    fn rounded_at(&self, last: i16) -> Self {
+        match self {
+            Floating::Normal { exponent, .. } => {
+                let rounded = self.rounded(*exponent - last + 1);
+                if let Floating::Normal { exponent, .. } = rounded {
+                    // Rounding may have carried into a new digit (or
+                    // rounded to zero), so pad to the right number of
+                    // digits.
+                    rounded.rounded(exponent - last + 1)
+                } else {
+                    rounded.rounded_at(last)
+                }
+            }
+            Floating::Zero { .. } if last < 0 => Floating::zero(-last as usize),
+            _ => self.clone(),
+        }
+    }
+
+    /// Split into a number between 1 and 1000 and an exponent that
+    /// is a multiple of three.
+    fn engineering(&self) -> (Self, i16) {
+        match self {
+            Floating::Normal {
+                exponent,
+                mantissa,
+                is_negative,
+            } => {
+                let e3 = exponent.div_euclid(3) * 3;
+                (
+                    Floating::Normal {
+                        exponent: exponent - e3,
+                        mantissa: mantissa.clone(),
+                        is_negative: *is_negative,
+                    },
+                    e3,
+                )
+            }
+            _ => (self.clone(), 0),
+        }
+    }
+
+    /// Format this floating point number in engineering notation,
+    /// in which the exponent is always a multiple of three.  `e` and
+    /// `after_e` are as in [Floating::fmt_with].
+    pub fn fmt_engineering(&self, f: &mut Formatter, e: &str, after_e: &str) -> Result<(), Error> {
+        self.fmt_engineered(f, e, after_e, Notation::PLAIN)
+    }
+
+    /// Format this floating point number just like
+    /// [Floating::fmt_engineering], but writing infinity as `F` does.
+    pub fn fmt_engineering_as<F: Format>(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+    ) -> Result<(), Error> {
+        self.fmt_engineered(f, e, after_e, Notation::plain::<F>())
+    }
+
+    fn fmt_engineered(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        p: Notation,
+    ) -> Result<(), Error> {
+        let (x, e3) = self.engineering();
+        x.fmt_digits(f, p)?;
+        if e3 != 0 {
+            f.write_str(e)?;
+            write!(f, "{}", e3)?;
+            f.write_str(after_e)?;
+        }
+        Ok(())
+    }
+
+    fn is_scientific(&self, e_waste: usize) -> bool {
+        let e_waste = e_waste as i16;
+        match self {
+            Floating::Normal { exponent, .. } => {
+                *exponent > 1 + e_waste || *exponent < -2 - e_waste
+            }
+            _ => false,
+        }
+    }
+
     /// Format this floating point number nicely, using `e` and
     /// `after_e` to delimit the exponent in case we decide to format
     /// it using scientific notation.  `e_waste` is the number
     /// of characters we consider wasted when using scientific
     /// notation.
+    ///
+    /// If the [Formatter] has a precision, it is interpreted as the
+    /// number of digits after the decimal point, just as for
+    /// [Display] of `f64` (or of the mantissa in scientific
+    /// notation).
     pub fn fmt_with(
         &self,
         f: &mut Formatter,
@@ -112,72 +397,823 @@ impl Floating {
         after_e: &str,
         e_waste: usize,
         power_ten: Option<&str>,
+    ) -> Result<(), Error> {
+        self.fmt_notated(f, e, after_e, e_waste, power_ten, Notation::PLAIN)
+    }
+
+    /// Format this floating point number just like
+    /// [Floating::fmt_with], but writing infinity as `F` does.
+    pub fn fmt_as<F: Format>(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        e_waste: usize,
+        power_ten: Option<&str>,
+    ) -> Result<(), Error> {
+        self.fmt_notated(f, e, after_e, e_waste, power_ten, Notation::plain::<F>())
+    }
+
+    /// Format this floating point number just like
+    /// [Floating::fmt_with], but using the decimal mark and digit
+    /// grouping of `locale`, punctuated as appropriate for `F`.
+    pub fn fmt_localized<F: Format>(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        e_waste: usize,
+        power_ten: Option<&str>,
+        locale: Locale,
+    ) -> Result<(), Error> {
+        self.fmt_notated(
+            f,
+            e,
+            after_e,
+            e_waste,
+            power_ten,
+            Notation::new::<F>(locale),
+        )
+    }
+
+    fn fmt_notated(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        e_waste: usize,
+        power_ten: Option<&str>,
+        p: Notation,
+    ) -> Result<(), Error> {
+        let scientific = self.is_scientific(e_waste);
+        match (self, f.precision()) {
+            (Floating::Zero { .. }, Some(digits)) => Floating::zero(digits).fmt_digits(f, p),
+            (Floating::Normal { .. }, Some(digits)) => {
+                // Round the number itself, as std does, rather than its
+                // shortest decimal representation, which would round
+                // twice.
+                let x = self.to_f64();
+                if scientific {
+                    Floating::from_exp_str(&format!("{:.*e}", digits, x))
+                        .fmt_scientific(f, e, after_e, power_ten, p)
+                } else {
+                    Floating::from_decimal_str(&format!("{:.*}", digits, x)).fmt_digits(f, p)
+                }
+            }
+            _ => {
+                if scientific {
+                    self.fmt_scientific(f, e, after_e, power_ten, p)
+                } else {
+                    self.fmt_digits(f, p)
+                }
+            }
+        }
+    }
+
+    fn fmt_scientific(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        power_ten: Option<&str>,
+        p: Notation,
     ) -> Result<(), Error> {
         match self {
-            Floating::Abnormal(s) => f.write_str(&s),
             Floating::Normal {
                 exponent,
                 mantissa,
                 is_negative,
             } => {
-                let e_waste = e_waste as i16;
                 if *is_negative {
                     f.write_str("-")?;
                 }
-                if *exponent > 1 + e_waste || *exponent < -2 - e_waste {
-                    if mantissa.len() > 1 {
-                        let (a, r) = mantissa.split_at(1);
-                        f.write_str(a)?;
-                        f.write_str(".")?;
-                        f.write_str(r)?;
-                        f.write_str(e)?;
-                        exponent.fmt(f)?;
-                        f.write_str(after_e)
-                    } else if mantissa == "1" && power_ten.is_some() {
-                        // We can omit the mantissa, keeping things
-                        // pretty and compact.
-                        f.write_str(power_ten.unwrap())?;
-                        exponent.fmt(f)?;
-                        f.write_str(after_e)
-                    } else {
-                        f.write_str(mantissa)?;
-                        f.write_str(e)?;
-                        exponent.fmt(f)?;
-                        f.write_str(after_e)
-                    }
+                if mantissa.len() > 1 {
+                    let (a, r) = mantissa.split_at(1);
+                    f.write_str(a)?;
+                    (p.write)(f, p.decimal_mark)?;
+                    f.write_str(r)?;
+                    f.write_str(e)?;
+                    write!(f, "{}", exponent)?;
+                    f.write_str(after_e)
+                } else if mantissa == "1" && power_ten.is_some() {
+                    // We can omit the mantissa, keeping things
+                    // pretty and compact.
+                    f.write_str(power_ten.unwrap())?;
+                    write!(f, "{}", exponent)?;
+                    f.write_str(after_e)
                 } else {
-                    if *exponent + 1 > mantissa.len() as i16 {
-                        f.write_str(mantissa)?;
-                        for _ in 0..*exponent as usize + 1 - mantissa.len() {
-                            f.write_str("0")?;
-                        }
-                        Ok(())
-                    } else if *exponent < 0 {
-                        f.write_str("0.")?;
-                        for _ in 0..-exponent - 1 {
-                            f.write_str("0")?;
-                        }
-                        f.write_str(&mantissa)
-                    } else if *exponent + 1 == mantissa.len() as i16 {
-                        f.write_str(mantissa)
-                    } else {
-                        let (a, b) = mantissa.split_at(*exponent as usize + 1);
-                        f.write_str(a)?;
-                        f.write_str(".")?;
-                        f.write_str(b)
+                    f.write_str(mantissa)?;
+                    f.write_str(e)?;
+                    write!(f, "{}", exponent)?;
+                    f.write_str(after_e)
+                }
+            }
+            _ => self.fmt_digits(f, p),
+        }
+    }
+
+    /// Format this floating point number without scientific notation,
+    /// no matter how large or small it is.
+    pub fn fmt_positional(&self, f: &mut Formatter) -> Result<(), Error> {
+        self.fmt_digits(f, Notation::PLAIN)
+    }
+
+    /// Format this floating point number just like
+    /// [Floating::fmt_positional], but writing infinity as `F` does.
+    pub fn fmt_positional_as<F: Format>(&self, f: &mut Formatter) -> Result<(), Error> {
+        self.fmt_digits(f, Notation::plain::<F>())
+    }
+
+    /// Format this floating point number without scientific notation,
+    /// using the decimal mark and digit grouping of `locale`.
+    pub fn fmt_positional_localized<F: Format>(
+        &self,
+        f: &mut Formatter,
+        locale: Locale,
+    ) -> Result<(), Error> {
+        self.fmt_digits(f, Notation::new::<F>(locale))
+    }
+
+    fn fmt_digits(&self, f: &mut Formatter, p: Notation) -> Result<(), Error> {
+        match self {
+            Floating::Zero { decimals, .. } => {
+                f.write_str("0")?;
+                if *decimals > 0 {
+                    (p.write)(f, p.decimal_mark)?;
+                    for _ in 0..*decimals {
+                        f.write_str("0")?;
+                    }
+                }
+                Ok(())
+            }
+            Floating::Infinite { negative } => {
+                if *negative {
+                    f.write_str("-")?;
+                }
+                (p.infinity)(f)
+            }
+            Floating::NaN => f.write_str("NaN"),
+            Floating::Normal {
+                exponent,
+                mantissa,
+                is_negative,
+            } => {
+                if *is_negative {
+                    f.write_str("-")?;
+                }
+                if *exponent + 1 >= mantissa.len() as i16 {
+                    let mut digits = mantissa.clone();
+                    for _ in 0..*exponent as usize + 1 - mantissa.len() {
+                        digits.push('0');
                     }
+                    p.write_grouped(f, &digits)
+                } else if *exponent < 0 {
+                    f.write_str("0")?;
+                    (p.write)(f, p.decimal_mark)?;
+                    for _ in 0..-exponent - 1 {
+                        f.write_str("0")?;
+                    }
+                    f.write_str(mantissa)
+                } else {
+                    let (a, b) = mantissa.split_at(*exponent as usize + 1);
+                    p.write_grouped(f, a)?;
+                    (p.write)(f, p.decimal_mark)?;
+                    f.write_str(b)
                 }
             }
         }
     }
 }
 
+/// How to write the parts of a number other than its digits: the
+/// decimal mark, digit group separators and infinity.
+#[derive(Copy, Clone)]
+struct Notation {
+    decimal_mark: char,
+    group_separator: Option<char>,
+    write: fn(&mut Formatter, char) -> Result<(), Error>,
+    infinity: fn(&mut Formatter) -> Result<(), Error>,
+}
+
+impl Notation {
+    const PLAIN: Notation = Notation {
+        decimal_mark: '.',
+        group_separator: None,
+        write: write_char,
+        infinity: write_inf,
+    };
+    fn plain<F: Format>() -> Self {
+        Notation {
+            infinity: F::infinity,
+            ..Notation::PLAIN
+        }
+    }
+    fn new<F: Format>(locale: Locale) -> Self {
+        Notation {
+            decimal_mark: locale.decimal_mark,
+            group_separator: locale.group_separator,
+            write: F::digit_separator,
+            infinity: F::infinity,
+        }
+    }
+    fn write_grouped(self, f: &mut Formatter, digits: &str) -> Result<(), Error> {
+        match self.group_separator {
+            Some(separator) if digits.len() > 3 => {
+                let (first, mut rest) = digits.split_at((digits.len() - 1) % 3 + 1);
+                f.write_str(first)?;
+                while !rest.is_empty() {
+                    let (group, tail) = rest.split_at(3);
+                    (self.write)(f, separator)?;
+                    f.write_str(group)?;
+                    rest = tail;
+                }
+                Ok(())
+            }
+            _ => f.write_str(digits),
+        }
+    }
+}
+
+fn write_char(f: &mut Formatter, c: char) -> Result<(), Error> {
+    f.write_char(c)
+}
+
+fn write_inf(f: &mut Formatter) -> Result<(), Error> {
+    f.write_str("inf")
+}
+
+/// The conventions for writing numbers in some locale: the decimal
+/// mark and the separator (if any) between groups of three digits.
+///
+/// ```
+/// use display_as::{HTML, LaTeX, Math, format_as};
+/// use display_as::float::{Decimals, Locale, Localized};
+/// assert_eq!(&format_as!(HTML, Localized::new(Decimals(1234567.89, 2), Locale::EN)).into_string(),
+///            "1,234,567.89");
+/// assert_eq!(&format_as!(HTML, Localized::new(Decimals(1234567.89, 2), Locale::DE)).into_string(),
+///            "1.234.567,89");
+/// assert_eq!(&format_as!(HTML, Localized::new(1.5e10, Locale::DE)).into_string(),
+///            "1,5×10<sup>10</sup>");
+/// assert_eq!(&format_as!(Math, Localized::new(1234.5, Locale::DE)).into_string(),
+///            "1.234{,}5");
+/// assert_eq!(&format_as!(LaTeX, Localized::new(1234567, Locale::FR)).into_string(),
+///            r"1\,234\,567");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Locale {
+    /// The character between the integer and fractional parts.
+    pub decimal_mark: char,
+    /// The character between groups of three digits, if any.
+    pub group_separator: Option<char>,
+}
+
+impl Locale {
+    /// No digit grouping and a decimal point, as in Rust itself.
+    pub const PLAIN: Locale = Locale {
+        decimal_mark: '.',
+        group_separator: None,
+    };
+    /// English, as in `1,234,567.89`.
+    pub const EN: Locale = Locale {
+        decimal_mark: '.',
+        group_separator: Some(','),
+    };
+    /// German (and much of continental Europe), as in `1.234.567,89`.
+    pub const DE: Locale = Locale {
+        decimal_mark: ',',
+        group_separator: Some('.'),
+    };
+    /// French, with a narrow no-break space between digit groups, as
+    /// in `1 234 567,89`.
+    pub const FR: Locale = Locale {
+        decimal_mark: ',',
+        group_separator: Some('\u{202F}'),
+    };
+    /// Swiss, as in `1'234'567.89`.
+    pub const CH: Locale = Locale {
+        decimal_mark: '.',
+        group_separator: Some('\''),
+    };
+    /// The SI style, with a narrow no-break space between digit
+    /// groups and a decimal point, as in `1 234 567.89`.
+    pub const SI: Locale = Locale {
+        decimal_mark: '.',
+        group_separator: Some('\u{202F}'),
+    };
+
+    /// Format an integer (or anything else that displays as a sequence
+    /// of digits with an optional sign) using this locale's digit
+    /// grouping, punctuated as appropriate for `F`.
+    pub fn fmt_integer<F: Format>(self, f: &mut Formatter, x: impl Display) -> Result<(), Error> {
+        let digits = x.to_string();
+        let digits = if let Some(digits) = digits.strip_prefix('-') {
+            f.write_str("-")?;
+            digits
+        } else {
+            &digits
+        };
+        Notation::new::<F>(self).write_grouped(f, digits)
+    }
+}
+
+/// A number displayed according to the conventions of a [Locale].
+///
+/// This works for integers, floats, [SigFigs] and [Decimals], and
+/// uses the same notation for exponents as the number would have
+/// without a locale.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Localized<T> {
+    /// The number itself
+    pub value: T,
+    /// How to punctuate the number
+    pub locale: Locale,
+}
+
+impl<T> Localized<T> {
+    /// Display `value` according to `locale`.
+    pub fn new(value: T, locale: Locale) -> Self {
+        Localized { value, locale }
+    }
+}
+
+/// A float displayed with a given number of significant figures.
+///
+/// The first field is the value, and the second is the number of
+/// significant figures.  Scientific notation is used (or not) just as
+/// for an ordinary float in each format.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct SigFigs(pub f64, pub usize);
+
+/// A float displayed with a given number of digits after the decimal
+/// point.
+///
+/// The first field is the value, and the second is the number of
+/// decimals.  Scientific notation is never used.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Decimals(pub f64, pub usize);
+
+/// How to display the uncertainty of a [Measured] value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Uncertainty {
+    /// Show the uncertainty in the last digits in parentheses, as in
+    /// `1.23(4)`.
+    Parenthetical,
+    /// Show the uncertainty with a plus-minus sign, as in `(1.23 ±
+    /// 0.04)`.
+    PlusMinus,
+}
+
+/// A float displayed in engineering notation, in which the exponent
+/// is always a multiple of three.
+///
+/// ```
+/// use display_as::{HTML, UTF8, format_as};
+/// use display_as::float::Engineering;
+/// assert_eq!(&format_as!(UTF8, Engineering::new(12345.)).into_string(), "12.345e3");
+/// assert_eq!(&format_as!(HTML, Engineering::new(4.7e-7).sig_figs(3)).into_string(),
+///            "470×10<sup>-9</sup>");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Engineering {
+    /// The value
+    pub value: f64,
+    /// The number of significant figures, if it is to be rounded
+    pub sig_figs: Option<usize>,
+}
+
+impl Engineering {
+    /// Display `value` in engineering notation.
+    pub fn new(value: f64) -> Self {
+        Engineering {
+            value,
+            sig_figs: None,
+        }
+    }
+    /// Round to the given number of significant figures.
+    pub fn sig_figs(self, digits: usize) -> Self {
+        Engineering {
+            sig_figs: Some(digits),
+            ..self
+        }
+    }
+    /// The value as a [Floating], rounded if requested.
+    pub fn floating(&self) -> Floating {
+        rounded_to_sig_figs(self.value, self.sig_figs)
+    }
+}
+
+fn rounded_to_sig_figs(value: f64, sig_figs: Option<usize>) -> Floating {
+    match sig_figs {
+        Some(digits) => Floating::with_sig_figs(value, digits),
+        None => Floating::from(value),
+    }
+}
+
+/// How to write the SI prefix of a [SiPrefix] value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PrefixStyle {
+    /// Unicode symbols, like `k` or `µ`.
+    Unicode,
+    /// Symbols in LaTeX math mode, like `k` or `\mu`.
+    Math,
+    /// Prefix macros from the LaTeX `siunitx` package, like
+    /// `\si{\kilo m}` or `\si{\micro s}`.
+    Siunitx,
+}
+
+const SI_PREFIXES: [(&str, &str); 17] = [
+    ("y", "yocto"),
+    ("z", "zepto"),
+    ("a", "atto"),
+    ("f", "femto"),
+    ("p", "pico"),
+    ("n", "nano"),
+    ("µ", "micro"),
+    ("m", "milli"),
+    ("", ""),
+    ("k", "kilo"),
+    ("M", "mega"),
+    ("G", "giga"),
+    ("T", "tera"),
+    ("P", "peta"),
+    ("E", "exa"),
+    ("Z", "zetta"),
+    ("Y", "yotta"),
+];
+
+pub(crate) fn si_prefix(exponent: i16) -> Option<(&'static str, &'static str)> {
+    let index = exponent / 3 + 8;
+    if exponent % 3 == 0 && (0..SI_PREFIXES.len() as i16).contains(&index) {
+        Some(SI_PREFIXES[index as usize])
+    } else {
+        None
+    }
+}
+
+impl PrefixStyle {
+    /// Write `unit`, escaped as `F`, with the prefix `symbol` (named
+    /// `name`) in this style.
+    pub(crate) fn write_prefixed<F: crate::Format>(
+        self,
+        f: &mut Formatter,
+        symbol: &str,
+        name: &str,
+        unit: &str,
+    ) -> Result<(), Error> {
+        match self {
+            _ if symbol.is_empty() => (),
+            PrefixStyle::Unicode => f.write_str(symbol)?,
+            PrefixStyle::Math if symbol == "µ" => f.write_str(r"\mu ")?,
+            PrefixStyle::Math => f.write_str(symbol)?,
+            PrefixStyle::Siunitx => {
+                // The prefix and unit must share one \si for siunitx
+                // to combine them.
+                f.write_str(r"\si{\")?;
+                f.write_str(name)?;
+                f.write_str(" ")?;
+                F::escape(f, unit)?;
+                return f.write_str("}");
+            }
+        }
+        F::escape(f, unit)
+    }
+}
+
+/// A float displayed with an SI prefix on its unit, as in `12.3 kB`
+/// or `4.7 µs`.
+///
+/// Values too large or small for an SI prefix are displayed in
+/// engineering notation.
+///
+/// ```
+/// use display_as::{HTML, LaTeX, Math, UTF8, format_as};
+/// use display_as::float::SiPrefix;
+/// assert_eq!(&format_as!(UTF8, SiPrefix::new(12345., "B").sig_figs(3)).into_string(), "12.3 kB");
+/// assert_eq!(&format_as!(HTML, SiPrefix::new(4.7e-6, "s")).into_string(), "4.7&nbsp;µs");
+/// assert_eq!(&format_as!(Math, SiPrefix::new(4.7e-6, "s")).into_string(), r"4.7\,\mathrm{\mu s}");
+/// assert_eq!(&format_as!(LaTeX, SiPrefix::new(4.7e-6, "s")).into_string(), r"4.7\,\si{\micro s}");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct SiPrefix<'a> {
+    /// The value
+    pub value: f64,
+    /// The unit, without any prefix
+    pub unit: &'a str,
+    /// The number of significant figures, if it is to be rounded
+    pub sig_figs: Option<usize>,
+}
+
+impl<'a> SiPrefix<'a> {
+    /// Display `value` with an SI prefix on `unit`.
+    pub fn new(value: f64, unit: &'a str) -> Self {
+        SiPrefix {
+            value,
+            unit,
+            sig_figs: None,
+        }
+    }
+    /// Round to the given number of significant figures.
+    pub fn sig_figs(self, digits: usize) -> Self {
+        SiPrefix {
+            sig_figs: Some(digits),
+            ..self
+        }
+    }
+
+    /// Format this value, writing `before_unit` and `after_unit`
+    /// around the prefixed (and escaped) unit.  `e` and `after_e` are
+    /// as in [Floating::fmt_with], and are only used if there is no
+    /// suitable prefix.
+    pub fn fmt_with<F: crate::Format>(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        before_unit: &str,
+        after_unit: &str,
+        prefixes: PrefixStyle,
+    ) -> Result<(), Error> {
+        let x = rounded_to_sig_figs(self.value, self.sig_figs);
+        let (mantissa, e3) = x.engineering();
+        if let Some((symbol, name)) = si_prefix(e3) {
+            mantissa.fmt_positional_as::<F>(f)?;
+            f.write_str(before_unit)?;
+            prefixes.write_prefixed::<F>(f, symbol, name, self.unit)?;
+        } else {
+            x.fmt_engineering_as::<F>(f, e, after_e)?;
+            f.write_str(before_unit)?;
+            F::escape(f, self.unit)?;
+        }
+        f.write_str(after_unit)
+    }
+}
+
+/// A value with an uncertainty.
+///
+/// The uncertainty is rounded to one significant figure (or two if
+/// its leading digit is a 1), and the value is rounded to the same
+/// decimal place.  Scientific notation is used (or not) just as for
+/// an ordinary float in each format.
+///
+/// ```
+/// use display_as::{HTML, Math, UTF8, format_as};
+/// use display_as::float::Measured;
+/// let c = Measured::new(299792.458, 3.7);
+/// assert_eq!(&format_as!(Math, c).into_string(), r"2.99792(4)\times10^{5}");
+/// assert_eq!(&format_as!(Math, c.plus_minus()).into_string(),
+///            r"(2.99792 \pm 0.00004)\times10^{5}");
+/// assert_eq!(&format_as!(UTF8, Measured::new(1.2345, 0.012)).into_string(), "1.234(12)");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Measured {
+    /// The value
+    pub value: f64,
+    /// The uncertainty of the value
+    pub uncertainty: f64,
+    /// How to display the uncertainty
+    pub style: Uncertainty,
+}
+
+impl Measured {
+    /// Create a value with an uncertainty, to be shown in parentheses.
+    pub fn new(value: f64, uncertainty: f64) -> Self {
+        Measured {
+            value,
+            uncertainty,
+            style: Uncertainty::Parenthetical,
+        }
+    }
+    /// Show the uncertainty with a plus-minus sign.
+    pub fn plus_minus(self) -> Self {
+        Measured {
+            style: Uncertainty::PlusMinus,
+            ..self
+        }
+    }
+
+    /// Format this value nicely, with `e`, `after_e` and `e_waste` as
+    /// in [Floating::fmt_with], and with `plus_minus` between the value
+    /// and its uncertainty for [Uncertainty::PlusMinus].
+    pub fn fmt_with(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        e_waste: usize,
+        plus_minus: &str,
+    ) -> Result<(), Error> {
+        self.fmt_notated(f, e, after_e, e_waste, plus_minus, Notation::PLAIN)
+    }
+
+    /// Format this value just like [Measured::fmt_with], but writing
+    /// infinity as `F` does.
+    pub fn fmt_as<F: Format>(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        e_waste: usize,
+        plus_minus: &str,
+    ) -> Result<(), Error> {
+        self.fmt_notated(f, e, after_e, e_waste, plus_minus, Notation::plain::<F>())
+    }
+
+    fn fmt_notated(
+        &self,
+        f: &mut Formatter,
+        e: &str,
+        after_e: &str,
+        e_waste: usize,
+        plus_minus: &str,
+        n: Notation,
+    ) -> Result<(), Error> {
+        let (u_exponent, u_mantissa) = match Floating::with_sig_figs(self.uncertainty.abs(), 1) {
+            Floating::Normal { mantissa, .. } if mantissa == "1" => {
+                match Floating::with_sig_figs(self.uncertainty.abs(), 2) {
+                    Floating::Normal {
+                        exponent, mantissa, ..
+                    } => (exponent, mantissa),
+                    _ => unreachable!(),
+                }
+            }
+            Floating::Normal {
+                mantissa, exponent, ..
+            } if self.uncertainty != 0. => (exponent, mantissa),
+            _ => {
+                // There is no meaningful uncertainty to show.
+                return Floating::from(self.value).fmt_notated(f, e, after_e, e_waste, None, n);
+            }
+        };
+        let last = u_exponent + 1 - u_mantissa.len() as i16;
+        let value = if last <= 0 {
+            Floating::with_decimals(self.value, -last as usize)
+        } else if self.value == 0. {
+            Floating::zero(0)
+        } else {
+            Floating::from(self.value).rounded_at(last)
+        };
+        let (v_exponent, v_mantissa, is_negative) = match value {
+            Floating::Normal {
+                exponent,
+                mantissa,
+                is_negative,
+            } => (exponent, mantissa, is_negative),
+            Floating::Zero { .. } => (0, "0".to_string(), false),
+            _ => {
+                return Floating::from(self.value).fmt_notated(f, e, after_e, e_waste, None, n);
+            }
+        };
+        let is_zero = v_mantissa.bytes().all(|c| c == b'0');
+        let exponent = if is_zero {
+            u_exponent
+        } else {
+            std::cmp::max(v_exponent, u_exponent)
+        };
+        let scientific = Floating::Normal {
+            exponent,
+            mantissa: "1".to_string(),
+            is_negative: false,
+        }
+        .is_scientific(e_waste);
+        let shift = if scientific { exponent } else { 0 };
+        let value = if is_zero {
+            Floating::zero(0).rounded_at(last - shift)
+        } else {
+            Floating::Normal {
+                exponent: v_exponent - shift,
+                mantissa: v_mantissa,
+                is_negative,
+            }
+        };
+        if self.style == Uncertainty::PlusMinus {
+            f.write_str("(")?;
+        }
+        value.fmt_digits(f, n)?;
+        match self.style {
+            Uncertainty::Parenthetical => {
+                f.write_str("(")?;
+                f.write_str(&u_mantissa)?;
+                for _ in shift..last {
+                    f.write_str("0")?;
+                }
+                f.write_str(")")?;
+            }
+            Uncertainty::PlusMinus => {
+                f.write_str(plus_minus)?;
+                Floating::Normal {
+                    exponent: u_exponent - shift,
+                    mantissa: u_mantissa,
+                    is_negative: false,
+                }
+                .fmt_digits(f, n)?;
+                f.write_str(")")?;
+            }
+        }
+        if scientific {
+            f.write_str(e)?;
+            write!(f, "{}", exponent)?;
+            f.write_str(after_e)?;
+        }
+        Ok(())
+    }
+}
+
+/// A value with a unit.
+///
+/// The value may be of any type that can be displayed, such as a
+/// float or a [Measured] value.  The unit is separated from the value
+/// by a thin or non-breaking space, and in [Math](crate::Math) is
+/// shown upright.
+///
+/// ```
+/// use display_as::{HTML, Math, format_as};
+/// use display_as::float::{Measured, Quantity};
+/// let v = Quantity::new(3e8, "m/s");
+/// assert_eq!(&format_as!(Math, v).into_string(), r"3\times10^{8}\,\mathrm{m/s}");
+/// assert_eq!(&format_as!(HTML, v).into_string(), "3×10<sup>8</sup>&nbsp;m&#x2f;s");
+/// let g = Quantity::new(Measured::new(9.81, 0.02).plus_minus(), "m/s²");
+/// assert_eq!(&format_as!(HTML, g).into_string(), "(9.81 ± 0.02)&nbsp;m&#x2f;s²");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Quantity<'a, T> {
+    /// The value
+    pub value: T,
+    /// The unit
+    pub unit: &'a str,
+}
+
+impl<'a, T> Quantity<'a, T> {
+    /// Create a value with a unit.
+    pub fn new(value: T, unit: &'a str) -> Self {
+        Quantity { value, unit }
+    }
+
+    /// Format this quantity, writing `before_unit` and `after_unit`
+    /// around the (escaped) unit.
+    pub fn fmt_with<F: crate::Format>(
+        &self,
+        f: &mut Formatter,
+        before_unit: &str,
+        after_unit: &str,
+    ) -> Result<(), Error>
+    where
+        T: crate::DisplayAs<F>,
+    {
+        self.value.fmt(f)?;
+        f.write_str(before_unit)?;
+        F::escape(f, self.unit)?;
+        f.write_str(after_unit)
+    }
+}
+
 impl Display for Floating {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        self.fmt_with(f, "e", "", 1, None)
+        self.fmt_notated(f, "e", "", 1, None, Notation::PLAIN)
     }
 }
 
+impl Display for Measured {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        self.fmt_notated(f, "e", "", 1, " ± ", Notation::PLAIN)
+    }
+}
+
+#[test]
+fn abnormal() {
+    assert_eq!(
+        Floating::from(0.0),
+        Floating::Zero {
+            negative: false,
+            decimals: 0
+        }
+    );
+    assert_eq!(
+        Floating::from(-0.0f32),
+        Floating::Zero {
+            negative: true,
+            decimals: 0
+        }
+    );
+    assert_eq!(
+        Floating::from(f64::NEG_INFINITY),
+        Floating::Infinite { negative: true }
+    );
+    assert_eq!(Floating::from(f32::NAN), Floating::NaN);
+    assert_eq!(
+        Floating::from(1e-310),
+        Floating::Normal {
+            exponent: -310,
+            mantissa: "1".to_string(),
+            is_negative: false
+        }
+    );
+    assert_eq!(&format!("{}", Floating::from(-0.0)), "0");
+    assert_eq!(&format!("{:.2}", Floating::from(-0.0)), "0.00");
+    assert_eq!(&format!("{}", Floating::from(-f64::INFINITY)), "-inf");
+    assert_eq!(&format!("{}", Floating::from(f64::NAN)), "NaN");
+    assert_eq!(&format!("{}", Floating::from(2.5e-320)), "2.5e-320");
+}
+
 #[test]
 fn display() {
     assert_eq!(&format!("{}", Floating::from(1.0)), "1");
@@ -192,3 +1228,92 @@ fn display() {
     assert_eq!(&format!("{}", Floating::from(0.0001)), "1e-4");
     assert_eq!(&format!("{}", Floating::from(0.001234)), "0.001234");
 }
+
+#[test]
+fn zeros() {
+    let zero = |decimals| Floating::Zero {
+        negative: false,
+        decimals,
+    };
+    assert_eq!(Floating::with_sig_figs(0.0, 2), zero(1));
+    assert_eq!(Floating::with_sig_figs(-0.0, 3), zero(2));
+    assert_eq!(Floating::with_decimals(0.0001, 2), zero(2));
+    assert_eq!(Floating::with_decimals(-0.001, 1), zero(1));
+    assert_eq!(Floating::from_decimal_str("0.00"), zero(2));
+    assert_eq!(Floating::from(0.04).rounded_at(-1), zero(1));
+    assert_eq!(Floating::from(4.0).rounded(-1), zero(0));
+    assert_eq!(&format!("{:.2}", Floating::from(-0.0)), "0.00");
+}
+
+#[test]
+fn sig_figs() {
+    assert_eq!(&format!("{}", Floating::with_sig_figs(1.0, 3)), "1.00");
+    assert_eq!(
+        &format!("{}", Floating::with_sig_figs(123456., 3)),
+        "1.23e5"
+    );
+    assert_eq!(
+        &format!("{}", Floating::with_sig_figs(-0.0012345, 2)),
+        "-0.0012"
+    );
+    assert_eq!(&format!("{}", Floating::with_sig_figs(9.996, 3)), "10.0");
+    assert_eq!(&format!("{}", Floating::with_sig_figs(0.0, 2)), "0.0");
+    assert_eq!(&format!("{}", Floating::with_sig_figs(1234., 2)), "1.2e3");
+    assert_eq!(&format!("{}", Floating::with_sig_figs(123.4, 2)), "120");
+}
+
+#[test]
+fn decimals() {
+    assert_eq!(&format!("{}", Floating::with_decimals(1.23456, 2)), "1.23");
+    assert_eq!(&format!("{}", Floating::with_decimals(0.005, 3)), "0.005");
+    assert_eq!(&format!("{}", Floating::with_decimals(0.0001, 2)), "0.00");
+    assert_eq!(&format!("{}", Floating::with_decimals(0.1, 2)), "0.10");
+    assert_eq!(
+        &format!("{}", Floating::with_decimals(-99.999, 2)),
+        "-100.00"
+    );
+    assert_eq!(&format!("{}", Floating::with_decimals(2.5, 0)), "2");
+}
+
+#[test]
+fn precision() {
+    assert_eq!(&format!("{:.2}", Floating::from(1.23456)), "1.23");
+    assert_eq!(&format!("{:.3}", Floating::from(1.0)), "1.000");
+    assert_eq!(&format!("{:.1}", Floating::from(0.96)), "1.0");
+    assert_eq!(&format!("{:.2}", Floating::from(0.0001)), "1.00e-4");
+    assert_eq!(&format!("{:.1}", Floating::from(1.26e7)), "1.3e7");
+    assert_eq!(&format!("{:.0}", Floating::from(9.6e7)), "1e8");
+    assert_eq!(&format!("{:.1}", Floating::from(0.04)), "0.0");
+    assert_eq!(&format!("{:.3}", Floating::from(1.2345)), "1.234");
+    assert_eq!(&format!("{:.0}", Floating::from(2.5)), "2");
+    assert_eq!(&format!("{:.0}", Floating::from(3.5)), "4");
+    assert_eq!(&format!("{:.2}", Floating::from(1.2345e-7)), "1.23e-7");
+}
+
+#[test]
+fn measured() {
+    let m = |v, u| format!("{}", Measured::new(v, u));
+    let pm = |v, u| format!("{}", Measured::new(v, u).plus_minus());
+    assert_eq!(&m(1.2345, 0.04), "1.23(4)");
+    assert_eq!(&pm(1.2345, 0.04), "(1.23 ± 0.04)");
+    assert_eq!(&m(1.2345, 0.14), "1.23(14)");
+    assert_eq!(&m(123456., 4000.), "1.23(4)e5");
+    assert_eq!(&pm(123456., 4000.), "(1.23 ± 0.04)e5");
+    assert_eq!(&m(1234., 50.), "1.23(5)e3");
+    assert_eq!(&m(123., 20.), "120(20)");
+    assert_eq!(&pm(-123., 20.), "(-120 ± 20)");
+    assert_eq!(&m(9.996, 0.04), "10.00(4)");
+    assert_eq!(&m(0.001, 0.04), "0.00(4)");
+    assert_eq!(&pm(0.0, 0.3), "(0.0 ± 0.3)");
+    assert_eq!(&m(1.5, 0.0), "1.5");
+}
+
+#[test]
+fn engineering() {
+    let eng = |x| format!("{}", Engineering::new(x).floating().engineering().1);
+    assert_eq!(&eng(1.0), "0");
+    assert_eq!(&eng(999.0), "0");
+    assert_eq!(&eng(1000.0), "3");
+    assert_eq!(&eng(0.5), "-3");
+    assert_eq!(&eng(1.2e-7), "-9");
+}
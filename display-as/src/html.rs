@@ -27,11 +27,14 @@ impl Format for HTML {
     }
     /// The MIME type for HTML is [mime::TEXT_HTML_UTF_8].
     fn mime() -> mime::Mime {
-        return mime::TEXT_HTML_UTF_8;
+        mime::TEXT_HTML_UTF_8
     }
     fn this_format() -> Self {
         HTML
     }
+    fn infinity(f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("∞")
+    }
 }
 
 macro_rules! display_as_from_display {
@@ -63,7 +66,28 @@ macro_rules! display_integers_as {
     };
 }
 
+/// Conveniently implement [DisplayAs] for `bool` and `char` for a new [Format].
+///
+/// A `bool` is displayed as `true` or `false`, while a `char` is
+/// escaped just like a one-character string.
+#[macro_export]
+macro_rules! display_bools_and_chars_as {
+    ($format:ty) => {
+        impl $crate::DisplayAs<$format> for bool {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                f.write_str(if *self { "true" } else { "false" })
+            }
+        }
+        impl $crate::DisplayAs<$format> for char {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                <$format as $crate::Format>::escape(f, self.encode_utf8(&mut [0; 4]))
+            }
+        }
+    };
+}
+
 display_integers_as!(HTML);
+display_bools_and_chars_as!(HTML);
 
 /// Inconveniently implement [DisplayAs] for floats for a new [Format].
 ///
@@ -101,18 +125,181 @@ macro_rules! display_floats_as {
     ($format:ty, $e:expr, $after_e:expr, $e_cost:expr, $power_ten:expr) => {
         impl $crate::DisplayAs<$format> for f64 {
             fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
-                $crate::float::Floating::from(*self).fmt_with(f, $e, $after_e, $e_cost, $power_ten)
+                $crate::float::Floating::from(*self)
+                    .fmt_as::<$format>(f, $e, $after_e, $e_cost, $power_ten)
             }
         }
         impl $crate::DisplayAs<$format> for f32 {
             fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
-                $crate::float::Floating::from(*self).fmt_with(f, $e, $after_e, $e_cost, $power_ten)
+                // A precision rounds the exact value, which widening
+                // to f64 keeps but the shortest f32 digits do not.
+                let x = if f.precision().is_some() {
+                    $crate::float::Floating::from(*self as f64)
+                } else {
+                    $crate::float::Floating::from(*self)
+                };
+                x.fmt_as::<$format>(f, $e, $after_e, $e_cost, $power_ten)
+            }
+        }
+        impl $crate::DisplayAs<$format> for $crate::float::SigFigs {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                $crate::float::Floating::with_sig_figs(self.0, self.1)
+                    .fmt_as::<$format>(f, $e, $after_e, $e_cost, $power_ten)
+            }
+        }
+        impl $crate::DisplayAs<$format> for $crate::float::Decimals {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                $crate::float::Floating::with_decimals(self.0, self.1).fmt_positional_as::<$format>(f)
+            }
+        }
+        impl $crate::DisplayAs<$format> for $crate::float::Engineering {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                self.floating().fmt_engineering_as::<$format>(f, $e, $after_e)
             }
         }
     };
 }
 display_floats_as!(HTML, "×10<sup>", "</sup>", 3, Some("10<sup>"));
 
+/// Implement [DisplayAs] for [Localized](float::Localized) numbers
+/// for a new [Format].
+///
+/// The arguments are the same as for [display_floats_as], so that a
+/// localized number uses the same exponent notation as any other.
+/// The decimal mark and digit group separators are written using
+/// [Format::digit_separator].
+#[macro_export]
+macro_rules! display_localized_as {
+    ($format:ty, $e:expr, $after_e:expr, $e_cost:expr, $power_ten:expr) => {
+        $crate::display_localized_as!(@integers $format,
+            i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+        $crate::display_localized_as!(@floats $format, $e, $after_e, $e_cost, $power_ten, f64, f32);
+        impl $crate::DisplayAs<$format> for $crate::float::Localized<$crate::float::SigFigs> {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                $crate::float::Floating::with_sig_figs(self.value.0, self.value.1)
+                    .fmt_localized::<$format>(f, $e, $after_e, $e_cost, $power_ten, self.locale)
+            }
+        }
+        impl $crate::DisplayAs<$format> for $crate::float::Localized<$crate::float::Decimals> {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                $crate::float::Floating::with_decimals(self.value.0, self.value.1)
+                    .fmt_positional_localized::<$format>(f, self.locale)
+            }
+        }
+    };
+    (@integers $format:ty, $($t:ty),*) => {
+        $(
+            impl $crate::DisplayAs<$format> for $crate::float::Localized<$t> {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                    self.locale.fmt_integer::<$format>(f, self.value)
+                }
+            }
+        )*
+    };
+    (@floats $format:ty, $e:expr, $after_e:expr, $e_cost:expr, $power_ten:expr, $($t:ty),*) => {
+        $(
+            impl $crate::DisplayAs<$format> for $crate::float::Localized<$t> {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                    $crate::float::Floating::from(self.value)
+                        .fmt_localized::<$format>(f, $e, $after_e, $e_cost, $power_ten, self.locale)
+                }
+            }
+        )*
+    };
+}
+display_localized_as!(HTML, "×10<sup>", "</sup>", 3, Some("10<sup>"));
+
+/// Implement [DisplayAs] for [Measured](float::Measured) values,
+/// [Quantity](float::Quantity) and [SiPrefix](float::SiPrefix) for a
+/// new [Format].
+///
+/// The first four arguments are as for [display_floats_as], followed
+/// by the plus-minus sign (with any spacing), the strings to write
+/// before and after the unit of a quantity, and the
+/// [PrefixStyle](float::PrefixStyle) for SI prefixes.  If the prefix
+/// style is omitted, Unicode symbols are used.
+#[macro_export]
+macro_rules! display_measurements_as {
+    ($format:ty, $e:expr, $after_e:expr, $e_cost:expr, $plus_minus:expr,
+     $before_unit:expr, $after_unit:expr) => {
+        $crate::display_measurements_as!(
+            $format,
+            $e,
+            $after_e,
+            $e_cost,
+            $plus_minus,
+            $before_unit,
+            $after_unit,
+            $crate::float::PrefixStyle::Unicode
+        );
+    };
+    ($format:ty, $e:expr, $after_e:expr, $e_cost:expr, $plus_minus:expr,
+     $before_unit:expr, $after_unit:expr, $prefixes:expr) => {
+        impl $crate::DisplayAs<$format> for $crate::float::Measured {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                self.fmt_as::<$format>(f, $e, $after_e, $e_cost, $plus_minus)
+            }
+        }
+        impl<'a, T: $crate::DisplayAs<$format>> $crate::DisplayAs<$format>
+            for $crate::float::Quantity<'a, T>
+        {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                self.fmt_with::<$format>(f, $before_unit, $after_unit)
+            }
+        }
+        impl<'a> $crate::DisplayAs<$format> for $crate::float::SiPrefix<'a> {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                self.fmt_with::<$format>(f, $e, $after_e, $before_unit, $after_unit, $prefixes)
+            }
+        }
+    };
+}
+display_measurements_as!(HTML, "×10<sup>", "</sup>", 3, " ± ", "&nbsp;", "");
+
+/// Implement [DisplayAs] for [ByteSize](human::ByteSize),
+/// [HumanDuration](human::HumanDuration) and
+/// [Duration](std::time::Duration) for a new [Format].
+///
+/// The arguments after the format are the strings to write before
+/// and after each unit, the string between the parts of a duration,
+/// and the [PrefixStyle](float::PrefixStyle) for SI prefixes.  If only
+/// the format is given, the [UTF8] text is escaped instead.
+#[macro_export]
+macro_rules! display_humanized_as {
+    ($format:ty, $before_unit:expr, $after_unit:expr, $between:expr, $prefixes:expr) => {
+        impl $crate::DisplayAs<$format> for $crate::human::ByteSize {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                self.fmt_with::<$format>(f, $before_unit, $after_unit, $prefixes)
+            }
+        }
+        impl $crate::DisplayAs<$format> for $crate::human::HumanDuration {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                self.fmt_with::<$format>(f, $before_unit, $after_unit, $between, $prefixes)
+            }
+        }
+        impl $crate::DisplayAs<$format> for ::std::time::Duration {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                $crate::human::HumanDuration::new(*self)
+                    .fmt_with::<$format>(f, $before_unit, $after_unit, $between, $prefixes)
+            }
+        }
+    };
+    ($format:ty) => {
+        $crate::display_humanized_as!(@escaped $format, $crate::human::ByteSize);
+        $crate::display_humanized_as!(@escaped $format, $crate::human::HumanDuration);
+        $crate::display_humanized_as!(@escaped $format, ::std::time::Duration);
+    };
+    (@escaped $format:ty, $type:ty) => {
+        impl $crate::DisplayAs<$format> for $type {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                let text = format!("{}", $crate::As::<$crate::UTF8, $type>::from(self));
+                <$format as $crate::Format>::escape(f, &text)
+            }
+        }
+    };
+}
+display_humanized_as!(HTML, "&nbsp;", "", " ", crate::float::PrefixStyle::Unicode);
+
 #[test]
 fn escaping() {
     assert_eq!(&format_as!(HTML, ("&")).into_string(), "&amp;");
@@ -126,9 +313,80 @@ fn escaping() {
     );
 }
 #[test]
+fn bools_and_chars() {
+    assert_eq!(&format_as!(HTML, false).into_string(), "false");
+    assert_eq!(&format_as!(HTML, 'a' " " '<').into_string(), "a &lt;");
+}
+#[test]
 fn floats() {
     assert_eq!(&format_as!(HTML, 3.0).into_string(), "3");
     assert_eq!(&format_as!(HTML, 3e5).into_string(), "3×10<sup>5</sup>");
     assert_eq!(&format_as!(HTML, 1e-6).into_string(), "10<sup>-6</sup>");
     assert_eq!(&format_as!(HTML, 3e4).into_string(), "30000");
+    assert_eq!(&format_as!(HTML, f32::INFINITY).into_string(), "∞");
+    assert_eq!(&format_as!(HTML, -0.0).into_string(), "0");
+    assert_eq!(
+        &format_as!(HTML, 5e-324).into_string(),
+        "5×10<sup>-324</sup>"
+    );
+}
+#[test]
+fn measurements() {
+    use crate::float::{Measured, Quantity};
+    assert_eq!(
+        &format_as!(HTML, Measured::new(123456., 4000.)).into_string(),
+        "1.23(4)×10<sup>5</sup>"
+    );
+    assert_eq!(
+        &format_as!(
+            HTML,
+            Quantity::new(Measured::new(123456., 4000.).plus_minus(), "m")
+        )
+        .into_string(),
+        "(1.23 ± 0.04)×10<sup>5</sup>&nbsp;m"
+    );
+}
+#[test]
+fn floats_with_precision() {
+    use crate::float::{Decimals, SigFigs};
+    assert_eq!(&format_as!(HTML, SigFigs(1e5, 3)).into_string(), "1.00×10<sup>5</sup>");
+    assert_eq!(&format_as!(HTML, SigFigs(1e5, 1)).into_string(), "10<sup>5</sup>");
+    assert_eq!(&format_as!(HTML, SigFigs(-123456., 3)).into_string(), "-1.23×10<sup>5</sup>");
+    assert_eq!(&format_as!(HTML, Decimals(123456.789, 1)).into_string(), "123456.8");
+    assert_eq!(&format!("{:.2}", As::<HTML, f64>::from(&3e5)), "3.00×10<sup>5</sup>");
+    assert_eq!(&format!("{:.3}", display(HTML, &1.2345)), "1.234");
+    assert_eq!(&format!("{:.0}", display(HTML, &2.5)), "2");
+    assert_eq!(&format!("{:.3}", display(HTML, &1.2345f32)), "1.235");
+    assert_eq!(
+        &format!("{:.3}", display(HTML, &1.2345f32)),
+        &format!("{:.3}", 1.2345f32)
+    );
+}
+#[test]
+fn localized() {
+    use crate::float::{Decimals, Locale, Localized, SigFigs};
+    assert_eq!(
+        &format_as!(HTML, Localized::new(-1234567, Locale::DE)).into_string(),
+        "-1.234.567"
+    );
+    assert_eq!(
+        &format_as!(HTML, Localized::new(123, Locale::EN)).into_string(),
+        "123"
+    );
+    assert_eq!(
+        &format_as!(HTML, Localized::new(1234.5f32, Locale::CH)).into_string(),
+        "1&#x27;234.5"
+    );
+    assert_eq!(
+        &format_as!(HTML, Localized::new(SigFigs(123456., 3), Locale::DE)).into_string(),
+        "1,23×10<sup>5</sup>"
+    );
+    assert_eq!(
+        &format_as!(HTML, Localized::new(Decimals(1234567.891, 2), Locale::FR)).into_string(),
+        "1\u{202F}234\u{202F}567,89"
+    );
+    assert_eq!(
+        &format_as!(HTML, Localized::new(0.05, Locale::DE)).into_string(),
+        "0,05"
+    );
 }
@@ -0,0 +1,241 @@
+//! Human-friendly durations and sizes in bytes, as in `1 h 23 min` or
+//! `4.2 MB`.
+//!
+//! ```
+//! use display_as::{HTML, UTF8, format_as};
+//! use display_as::human::{ByteSize, HumanDuration};
+//! use std::time::Duration;
+//! assert_eq!(&format_as!(UTF8, ByteSize::new(4_200_000)).into_string(), "4.2 MB");
+//! assert_eq!(&format_as!(UTF8, ByteSize::new(4_200_000).binary()).into_string(), "4.0 MiB");
+//! assert_eq!(&format_as!(HTML, Duration::from_secs(5000)).into_string(),
+//!            "1&nbsp;h 23&nbsp;min");
+//! assert_eq!(&format_as!(UTF8, HumanDuration::new(Duration::from_secs(5000)).units(3))
+//!                .into_string(),
+//!            "1 h 23 min 20 s");
+//! ```
+
+use crate::float::{si_prefix, Floating, PrefixStyle};
+use crate::Format;
+use std::fmt::{Error, Formatter};
+use std::time::Duration;
+
+/// A number of bytes, displayed with a decimal (`kB`, `MB`, ...) or
+/// binary (`KiB`, `MiB`, ...) prefix.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ByteSize {
+    /// The number of bytes
+    pub bytes: u64,
+    /// Use binary prefixes, which are powers of 1024?
+    pub binary: bool,
+    /// The number of digits after the decimal point
+    pub decimals: usize,
+}
+
+const BINARY_PREFIXES: [&str; 7] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"];
+
+impl ByteSize {
+    /// Display `bytes` with a decimal prefix and one decimal.
+    pub fn new(bytes: u64) -> Self {
+        ByteSize {
+            bytes,
+            binary: false,
+            decimals: 1,
+        }
+    }
+    /// Use binary prefixes, which are powers of 1024.
+    pub fn binary(self) -> Self {
+        ByteSize {
+            binary: true,
+            ..self
+        }
+    }
+    /// Show the given number of digits after the decimal point.
+    pub fn decimals(self, decimals: usize) -> Self {
+        ByteSize { decimals, ..self }
+    }
+
+    /// The size as a number and the power of 1000 (or 1024) it is in.
+    fn scaled(&self) -> (f64, usize) {
+        let base = if self.binary { 1024. } else { 1000. };
+        let mut value = self.bytes as f64;
+        let mut power = 0;
+        while power + 1 < BINARY_PREFIXES.len() {
+            // Compare after rounding, so we never show `1000.0 kB`.
+            let rounded = format!("{:.*}", self.decimals, value);
+            if rounded.parse::<f64>().unwrap_or(value) < base {
+                break;
+            }
+            value /= base;
+            power += 1;
+        }
+        (value, power)
+    }
+
+    /// Format this size, writing `before_unit` and `after_unit` around
+    /// the unit, and using `prefixes` for decimal prefixes.
+    pub fn fmt_with<F: Format>(
+        &self,
+        f: &mut Formatter,
+        before_unit: &str,
+        after_unit: &str,
+        prefixes: PrefixStyle,
+    ) -> Result<(), Error> {
+        let (value, power) = self.scaled();
+        if power == 0 {
+            write!(f, "{}", self.bytes)?;
+        } else {
+            Floating::with_decimals(value, self.decimals).fmt_positional_as::<F>(f)?;
+        }
+        f.write_str(before_unit)?;
+        if self.binary {
+            F::escape(f, BINARY_PREFIXES[power])?;
+            F::escape(f, "B")?;
+        } else {
+            let (symbol, name) = si_prefix(3 * power as i16).unwrap_or(("", ""));
+            prefixes.write_prefixed::<F>(f, symbol, name, "B")?;
+        }
+        f.write_str(after_unit)
+    }
+}
+
+/// A duration displayed in its largest units, as in `1 h 23 min`.
+///
+/// Only the given number of units is shown (two by default), and
+/// anything smaller is left off.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HumanDuration {
+    /// The duration
+    pub duration: Duration,
+    /// The number of units to show
+    pub units: usize,
+}
+
+const DURATION_UNITS: [(u128, &str, &str, &str); 7] = [
+    (24 * 60 * 60 * 1_000_000_000, "", "", "d"),
+    (60 * 60 * 1_000_000_000, "", "", "h"),
+    (60 * 1_000_000_000, "", "", "min"),
+    (1_000_000_000, "", "", "s"),
+    (1_000_000, "m", "milli", "s"),
+    (1_000, "µ", "micro", "s"),
+    (1, "n", "nano", "s"),
+];
+
+impl HumanDuration {
+    /// Display `duration` in its two largest units.
+    pub fn new(duration: Duration) -> Self {
+        HumanDuration { duration, units: 2 }
+    }
+    /// Show the given number of units.
+    pub fn units(self, units: usize) -> Self {
+        HumanDuration {
+            units: std::cmp::max(units, 1),
+            ..self
+        }
+    }
+
+    /// Format this duration, writing `before_unit` and `after_unit`
+    /// around each unit, `between` the parts, and using `prefixes` for
+    /// the prefixes of units smaller than a second.
+    pub fn fmt_with<F: Format>(
+        &self,
+        f: &mut Formatter,
+        before_unit: &str,
+        after_unit: &str,
+        between: &str,
+        prefixes: PrefixStyle,
+    ) -> Result<(), Error> {
+        let mut nanos = self.duration.as_nanos();
+        if nanos == 0 {
+            f.write_str("0")?;
+            f.write_str(before_unit)?;
+            F::escape(f, "s")?;
+            return f.write_str(after_unit);
+        }
+        let first = DURATION_UNITS
+            .iter()
+            .position(|u| nanos >= u.0)
+            .expect("nanoseconds are the smallest unit");
+        let mut is_first = true;
+        for &(size, symbol, name, unit) in DURATION_UNITS[first..].iter().take(self.units) {
+            let count = nanos / size;
+            nanos %= size;
+            if count == 0 {
+                continue;
+            }
+            if !is_first {
+                f.write_str(between)?;
+            }
+            is_first = false;
+            write!(f, "{}", count)?;
+            f.write_str(before_unit)?;
+            prefixes.write_prefixed::<F>(f, symbol, name, unit)?;
+            f.write_str(after_unit)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn byte_sizes() {
+    use crate::{format_as, LaTeX, HTML, UTF8};
+    assert_eq!(&format_as!(UTF8, ByteSize::new(999)).into_string(), "999 B");
+    assert_eq!(
+        &format_as!(UTF8, ByteSize::new(999_960)).into_string(),
+        "1.0 MB"
+    );
+    assert_eq!(
+        &format_as!(UTF8, ByteSize::new(1536).binary().decimals(2)).into_string(),
+        "1.50 KiB"
+    );
+    assert_eq!(
+        &format_as!(HTML, ByteSize::new(u64::MAX)).into_string(),
+        "18.4&nbsp;EB"
+    );
+    assert_eq!(
+        &format_as!(LaTeX, ByteSize::new(1500)).into_string(),
+        r"1.5\,\si{\kilo B}"
+    );
+}
+
+#[test]
+fn durations() {
+    use crate::{format_as, LaTeX, Math, Rust, URL, UTF8};
+    let d = Duration::new(90061, 5_000_000);
+    assert_eq!(&format_as!(UTF8, d).into_string(), "1 d 1 h");
+    assert_eq!(
+        &format_as!(UTF8, HumanDuration::new(d).units(5)).into_string(),
+        "1 d 1 h 1 min 1 s 5 ms"
+    );
+    assert_eq!(
+        &format_as!(UTF8, Duration::from_secs(3601)).into_string(),
+        "1 h"
+    );
+    assert_eq!(
+        &format_as!(UTF8, Duration::from_nanos(1500)).into_string(),
+        "1 µs 500 ns"
+    );
+    assert_eq!(
+        &format_as!(UTF8, Duration::from_secs(0)).into_string(),
+        "0 s"
+    );
+    assert_eq!(
+        &format_as!(Math, Duration::from_micros(2)).into_string(),
+        r"2\,\mathrm{\mu s}"
+    );
+    assert_eq!(
+        &format_as!(LaTeX, Duration::from_secs(61)).into_string(),
+        r"1\,min 1\,s"
+    );
+    assert_eq!(
+        &format_as!(LaTeX, Duration::from_micros(2)).into_string(),
+        r"2\,\si{\micro s}"
+    );
+    assert_eq!(
+        &format_as!(Rust, Duration::from_secs(61)).into_string(),
+        r#""1 min 1 s""#
+    );
+    assert_eq!(
+        &format_as!(URL, Duration::from_secs(61)).into_string(),
+        "1%20min%201%20s"
+    );
+}
@@ -0,0 +1,242 @@
+//! Display the items of an iterator separated by a separator.
+
+use super::*;
+
+/// Display the items of an iterator, with separators between them.
+///
+/// This is created by [`join`], [`join_last`] or [`join_oxford`].
+#[derive(Clone)]
+pub struct Join<I, S, L = S> {
+    items: I,
+    separator: S,
+    last: Option<L>,
+    oxford: bool,
+}
+
+/// A string that is included in any [`Format`] without escaping.
+///
+/// This is convenient for separators like `", "` or `"<br/>"`, which
+/// you will not usually want escaped.  Be careful, since the contents
+/// are output verbatim.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Raw<'a>(pub &'a str);
+
+impl<'a, F: Format> DisplayAs<F> for Raw<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str(self.0)
+    }
+    fn estimate_size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// A slice whose items are displayed separated by commas.
+///
+/// This is the same as [`join`] with a separator of `Raw(", ")`, and
+/// is a convenient way to display a slice or `Vec` directly.
+///
+/// ```
+/// use display_as::{HTML, Slice, format_as};
+/// let primes = vec![2, 3, 5];
+/// assert_eq!(&format_as!(HTML, Slice(&primes)).into_string(), "2, 3, 5");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Slice<'a, T>(pub &'a [T]);
+
+impl<'a, F: Format, T: DisplayAs<F>> DisplayAs<F> for Slice<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        join(self.0, Raw(", ")).fmt(f)
+    }
+    fn estimate_size(&self) -> usize {
+        DisplayAs::<F>::estimate_size(&join(self.0, Raw(", ")))
+    }
+}
+
+/// Display each item of `items` with `separator` between them.
+///
+/// The `items` may be anything that can be iterated over repeatedly,
+/// such as a `&Vec` or a slice.  The separator must itself implement
+/// [`DisplayAs`], so a plain string will be escaped.  Use [`Raw`] if
+/// you want the separator to be output verbatim.
+///
+/// ```
+/// use display_as::{HTML, Raw, format_as, join};
+/// let names = vec!["Alice", "Bob & Carol"];
+/// assert_eq!(&format_as!(HTML, join(&names, ", ")).into_string(),
+///            "Alice, Bob &amp; Carol");
+/// assert_eq!(&format_as!(HTML, join(&names, Raw("<br/>"))).into_string(),
+///            "Alice<br/>Bob &amp; Carol");
+/// assert_eq!(&format_as!(HTML, join(1..4, "/")).into_string(),
+///            "1&#x2f;2&#x2f;3");
+/// ```
+pub fn join<I: IntoIterator + Clone, S>(items: I, separator: S) -> Join<I, S> {
+    Join {
+        items,
+        separator,
+        last: None,
+        oxford: false,
+    }
+}
+
+/// Display each item of `items` with `separator` between them, but
+/// with `last` before the final item.
+///
+/// ```
+/// use display_as::{HTML, Raw, format_as, join_last};
+/// let names = ["Alice", "Bob", "Carol"];
+/// assert_eq!(&format_as!(HTML, join_last(&names, Raw(", "), Raw(" and "))).into_string(),
+///            "Alice, Bob and Carol");
+/// assert_eq!(&format_as!(HTML, join_last(&names[1..], Raw(", "), Raw(" and "))).into_string(),
+///            "Bob and Carol");
+/// ```
+pub fn join_last<I: IntoIterator + Clone, S, L>(items: I, separator: S, last: L) -> Join<I, S, L> {
+    Join {
+        items,
+        separator,
+        last: Some(last),
+        oxford: false,
+    }
+}
+
+/// Display each item of `items` as an English list with an "oxford
+/// comma" and the given `conjunction` (e.g. "and" or "or").
+///
+/// ```
+/// use display_as::{HTML, format_as, join_oxford};
+/// let names = ["Alice", "Bob", "Carol"];
+/// assert_eq!(&format_as!(HTML, join_oxford(&names, "and")).into_string(),
+///            "Alice, Bob, and Carol");
+/// assert_eq!(&format_as!(HTML, join_oxford(&names[1..], "or")).into_string(),
+///            "Bob or Carol");
+/// ```
+pub fn join_oxford<I: IntoIterator + Clone, L>(
+    items: I,
+    conjunction: L,
+) -> Join<I, Raw<'static>, L> {
+    Join {
+        items,
+        separator: Raw(", "),
+        last: Some(conjunction),
+        oxford: true,
+    }
+}
+
+impl<F, I, S, L> DisplayAs<F> for Join<I, S, L>
+where
+    F: Format,
+    I: IntoIterator + Clone,
+    I::Item: DisplayAs<F>,
+    S: DisplayAs<F>,
+    L: DisplayAs<F>,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let mut items = self.items.clone().into_iter().peekable();
+        let mut count = 0;
+        while let Some(x) = items.next() {
+            if count > 0 {
+                match &self.last {
+                    Some(last) if items.peek().is_none() => {
+                        if self.oxford {
+                            if count > 1 {
+                                self.separator.fmt(f)?;
+                            } else {
+                                f.write_str(" ")?;
+                            }
+                            last.fmt(f)?;
+                            f.write_str(" ")?;
+                        } else {
+                            last.fmt(f)?;
+                        }
+                    }
+                    _ => self.separator.fmt(f)?,
+                }
+            }
+            x.fmt(f)?;
+            count += 1;
+        }
+        Ok(())
+    }
+    fn estimate_size(&self) -> usize {
+        let mut size = 0;
+        let mut count = 0;
+        for x in self.items.clone() {
+            size += x.estimate_size();
+            count += 1;
+        }
+        if count > 1 {
+            size += (count - 1) * self.separator.estimate_size();
+        }
+        size
+    }
+}
+
+#[test]
+fn joining() {
+    let empty: Vec<usize> = Vec::new();
+    assert_eq!(&format_as!(HTML, join(&empty, ", ")).into_string(), "");
+    assert_eq!(&format_as!(HTML, join(&[1], ", ")).into_string(), "1");
+    assert_eq!(
+        &format_as!(LaTeX, join(&[1, 2, 3], "&")).into_string(),
+        r"1\&2\&3"
+    );
+    assert_eq!(
+        &format_as!(Math, join(&[1e5, 2e5], Raw(", "))).into_string(),
+        r"10^{5}, 2\times10^{5}"
+    );
+    assert_eq!(
+        &format_as!(
+            HTML,
+            join(["a", "b"].iter().map(|s| s.to_uppercase()), Raw("+"))
+        )
+        .into_string(),
+        "A+B"
+    );
+}
+
+#[test]
+fn slices() {
+    let empty: [&str; 0] = [];
+    assert_eq!(&format_as!(HTML, Slice(&empty)).into_string(), "");
+    assert_eq!(
+        &format_as!(HTML, Slice(&["a<b", "c"])).into_string(),
+        "a&lt;b, c"
+    );
+    let v = vec![1.5, 2e10];
+    assert_eq!(
+        &format_as!(LaTeX, Slice(&v)).into_string(),
+        r"1.5, 2$\times10^{10}$"
+    );
+}
+
+#[test]
+fn estimated_sizes() {
+    let x = join(&["ab", "cde"], Raw(", "));
+    assert_eq!(DisplayAs::<HTML>::estimate_size(&x), 4 + 4 + 2);
+    let empty: Vec<&str> = Vec::new();
+    assert_eq!(
+        DisplayAs::<HTML>::estimate_size(&join(&empty, Raw(", "))),
+        0
+    );
+    assert_eq!(DisplayAs::<HTML>::estimate_size(&Slice(&["ab"])), 4);
+}
+
+#[test]
+fn joining_prose() {
+    let names = ["Alice", "Bob", "Carol", "Dave"];
+    assert_eq!(
+        &format_as!(HTML, join_oxford(&names[..1], "and")).into_string(),
+        "Alice"
+    );
+    assert_eq!(
+        &format_as!(HTML, join_oxford(&names, "and")).into_string(),
+        "Alice, Bob, Carol, and Dave"
+    );
+    assert_eq!(
+        &format_as!(HTML, join_last(&names, Raw(", "), Raw(" & "))).into_string(),
+        "Alice, Bob, Carol & Dave"
+    );
+    assert_eq!(
+        &format_as!(HTML, join_last(&names[..2], Raw(", "), " & ")).into_string(),
+        "Alice &amp; Bob"
+    );
+}
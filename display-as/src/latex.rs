@@ -7,54 +7,123 @@ use super::*;
 pub struct LaTeX;
 impl Format for LaTeX {
     fn mime() -> mime::Mime {
-        return "text/x-latex".parse().unwrap();
+        "text/x-latex".parse().unwrap()
     }
     fn this_format() -> Self {
         LaTeX
     }
+    fn infinity(f: &mut Formatter) -> Result<(), Error> {
+        f.write_str(r"$\infty$")
+    }
+    /// Characters that are special in LaTeX are escaped, as are `<`,
+    /// `>`, `|` and `"`, which would otherwise come out as different
+    /// glyphs in the default OT1 font encoding.  A non-breaking space
+    /// is written as `~`.
     fn escape(f: &mut Formatter, mut s: &str) -> Result<(), Error> {
-        let badstuff = "&{}#%\\~$_^";
+        let badstuff = "&{}#%\\~$_^<>|\"\u{A0}";
         while let Some(idx) = s.find(|c| badstuff.contains(c)) {
             let (first, rest) = s.split_at(idx);
-            let (badchar, tail) = rest.split_at(1);
+            let badchar = rest.chars().next().unwrap();
+            let tail = &rest[badchar.len_utf8()..];
             f.write_str(first)?;
             f.write_str(match badchar {
-                "&" => r"\&",
-                "{" => r"\{",
-                "}" => r"\}",
-                "#" => r"\#",
-                "%" => r"\%",
-                "\\" => r"\textbackslash{}",
-                "~" => r"\textasciitilde{}",
-                "$" => r"\$",
-                "_" => r"\_",
-                "^" => r"\^",
+                '&' => r"\&",
+                '{' => r"\{",
+                '}' => r"\}",
+                '#' => r"\#",
+                '%' => r"\%",
+                '\\' => r"\textbackslash{}",
+                '~' => r"\textasciitilde{}",
+                '$' => r"\$",
+                '_' => r"\_",
+                '^' => r"\textasciicircum{}",
+                '<' => r"\textless{}",
+                '>' => r"\textgreater{}",
+                '|' => r"\textbar{}",
+                '"' => r"\textquotedbl{}",
+                '\u{A0}' => "~",
                 _ => unreachable!(),
             })?;
             s = tail;
         }
         f.write_str(s)
     }
+    fn digit_separator(f: &mut Formatter, c: char) -> Result<(), Error> {
+        match c {
+            '\u{202F}' | '\u{2009}' => f.write_str(r"\,"),
+            _ => Self::escape(f, c.encode_utf8(&mut [0; 4])),
+        }
+    }
 }
 
 display_integers_as!(LaTeX);
+display_bools_and_chars_as!(LaTeX);
 display_floats_as!(LaTeX, r"$\times10^{", "}$", 3, Some("$10^{"));
+display_localized_as!(LaTeX, r"$\times10^{", "}$", 3, Some("$10^{"));
+display_measurements_as!(
+    LaTeX,
+    r"$\times10^{",
+    "}$",
+    3,
+    r"${}\pm{}$",
+    r"\,",
+    "",
+    crate::float::PrefixStyle::Siunitx
+);
+display_humanized_as!(LaTeX, r"\,", "", " ", crate::float::PrefixStyle::Siunitx);
 
 #[test]
 fn escaping() {
     assert_eq!(&format_as!(LaTeX, ("&")).into_string(), r"\&");
     assert_eq!(
         &format_as!(LaTeX, ("hello &>this is cool")).into_string(),
-        r"hello \&>this is cool"
+        r"hello \&\textgreater{}this is cool"
     );
     assert_eq!(
         &format_as!(LaTeX, ("hello &>this is 'cool")).into_string(),
-        r"hello \&>this is 'cool"
+        r"hello \&\textgreater{}this is 'cool"
     );
 }
 #[test]
+fn reference_table() {
+    let table = [
+        ("&", r"\&"),
+        ("{", r"\{"),
+        ("}", r"\}"),
+        ("#", r"\#"),
+        ("%", r"\%"),
+        ("\\", r"\textbackslash{}"),
+        ("~", r"\textasciitilde{}"),
+        ("$", r"\$"),
+        ("_", r"\_"),
+        ("^", r"\textasciicircum{}"),
+        ("<", r"\textless{}"),
+        (">", r"\textgreater{}"),
+        ("|", r"\textbar{}"),
+        ("\"", r"\textquotedbl{}"),
+        ("a\u{A0}b", "a~b"),
+        ("é", "é"),
+    ];
+    for (input, latex) in table.iter() {
+        assert_eq!(&format_as!(LaTeX, input).into_string(), latex);
+    }
+}
+#[test]
 fn floats() {
     assert_eq!(&format_as!(LaTeX, 3.0).into_string(), "3");
     assert_eq!(&format_as!(LaTeX, 3e5).into_string(), r"3$\times10^{5}$");
     assert_eq!(&format_as!(LaTeX, 3e4).into_string(), "30000");
+    assert_eq!(&format_as!(LaTeX, f64::INFINITY).into_string(), r"$\infty$");
+}
+#[test]
+fn measurements() {
+    use crate::float::{Measured, Quantity};
+    assert_eq!(
+        &format_as!(
+            LaTeX,
+            Quantity::new(Measured::new(123456., 4000.).plus_minus(), "m_0")
+        )
+        .into_string(),
+        r"(1.23${}\pm{}$0.04)$\times10^{5}$\,m\_0"
+    );
 }
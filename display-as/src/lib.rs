@@ -20,7 +20,8 @@
 //! using these Formats, rather than on defining your own (which also
 //! isn't too hard).  A format is a zero-size type that has a rule for
 //! escaping strings and an associated MIME type.  The builtin formats
-//! include [`HTML`], [`LaTeX`], and [`Math`] (which is math-mode LaTeX).
+//! include [`HTML`], [`LaTeX`], [`Math`] (which is math-mode LaTeX),
+//! [`MathML`] and [`CSV`].
 //!
 //! ## [`DisplayAs`]`<F>`
 //!
@@ -234,23 +235,80 @@ pub use display_as_proc_macro::write_as;
 /// Can I write doc here?
 pub use display_as_proc_macro::with_template;
 
+/// Like [`with_template`], but also make the type a web response for
+/// each web framework feature that is enabled, such as `axum`, so
+/// that a handler can return it directly.
+///
+/// A type is a response in only one format, so use
+/// `with_response_template` on one `DisplayAs` impl of a type and
+/// [`with_template`] on any others.  To choose the format for each
+/// request, respond with a [`Negotiate`] instead.
+///
+/// ```compile_fail,E0119
+/// use display_as::{with_response_template, DisplayAs, HTML, UTF8};
+/// struct A;
+/// #[with_response_template("a")]
+/// impl DisplayAs<HTML> for A {}
+/// #[with_response_template("a")]
+/// impl DisplayAs<UTF8> for A {}
+/// ```
+///
+/// If the format is a type parameter, there is no one format to
+/// respond with, and this is the same as [`with_template`].
+pub use display_as_proc_macro::with_response_template;
+
+use std::borrow::Cow;
 use std::fmt::{Display, Error, Formatter};
+use std::rc::Rc;
+use std::sync::Arc;
 
 #[macro_use]
 mod html;
+mod csv;
+mod join;
 mod latex;
+mod markdown;
 mod mathlatex;
+mod mathml;
+mod matrix;
+mod negotiate;
+#[cfg(any(
+    feature = "rust_decimal",
+    feature = "num-bigint",
+    feature = "num-rational",
+    feature = "num-complex"
+))]
+mod numeric;
+mod response;
 mod rust;
+mod system;
+mod table;
+mod transliterate;
 mod url;
 mod utf8;
 
+pub mod expr;
 pub mod float;
+pub mod human;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod datetime;
 
+pub use crate::csv::CSV;
 pub use crate::html::HTML;
+pub use crate::join::{join, join_last, join_oxford, Join, Raw, Slice};
 pub use crate::latex::LaTeX;
+pub use crate::markdown::Markdown;
 pub use crate::mathlatex::Math;
+pub use crate::mathml::MathML;
+pub use crate::matrix::Matrix;
+pub use crate::negotiate::Negotiate;
+pub use crate::response::Response;
 pub use crate::rust::Rust;
-pub use crate::url::URL;
+pub use crate::table::{Align, Cell, Table};
+pub use crate::transliterate::AsciiLaTeX;
+pub use crate::url::{
+    FormEncoded, PathSegment, QueryComponent, QueryString, UnsafeScheme, Url, SAFE_SCHEMES, URL,
+};
 pub use crate::utf8::UTF8;
 
 /// Format is a format that we can use for displaying data.
@@ -265,6 +323,18 @@ pub trait Format: Sync + Send + Copy + Eq + Ord + std::hash::Hash {
     fn mime() -> mime::Mime;
     /// Return an actual [`Format`] for use in [`As`] below.
     fn this_format() -> Self;
+    /// Write a character that punctuates the digits of a number,
+    /// such as a decimal mark or a thousands separator.  By default
+    /// this is just escaped, but a format may need something special,
+    /// as with `{,}` for a decimal comma in LaTeX math mode.
+    fn digit_separator(f: &mut Formatter, c: char) -> Result<(), Error> {
+        Self::escape(f, c.encode_utf8(&mut [0; 4]))
+    }
+    /// Write an infinite number (after its sign, if negative).  By
+    /// default this is `inf`, just as for [Display](std::fmt::Display).
+    fn infinity(f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("inf")
+    }
 }
 
 /// This trait is analogous to [Display](std::fmt::Display), but will display the data in
@@ -279,7 +349,10 @@ pub trait DisplayAs<F: Format> {
     }
 
     /// Creates a display object
-    fn display<'a>(&'a self) -> As<'a, F, Self> {
+    fn display<'a>(&'a self) -> As<'a, F, Self>
+    where
+        Self: Sized,
+    {
         As::from(self)
     }
 }
@@ -309,12 +382,12 @@ impl<F: Format, C: Fn(&mut Formatter) -> Result<(), Error>> DisplayAs<F> for Clo
 }
 impl<F> PartialEq<FormattedString<F>> for str {
     fn eq(&self, other: &FormattedString<F>) -> bool {
-        self == &other.inner
+        self == other.inner
     }
 }
 impl<F> PartialEq<str> for FormattedString<F> {
     fn eq(&self, other: &str) -> bool {
-        &self.inner == other
+        self.inner == other
     }
 }
 #[test]
@@ -356,47 +429,306 @@ impl<'a, F: Format, T: DisplayAs<F> + ?Sized> Display for As<'a, F, T> {
 /// type into a [rouille::Response].  Note that it is necessary to be
 /// explicit about the format because a given type `T` may be
 /// displayed in multiple different formats.
+///
+/// A response cannot see the request, so a [`Negotiate`] must be
+/// given its `Accept` header, as in `Negotiate::new(x,
+/// request.header("Accept"))`.  Otherwise any format is acceptable.
 #[cfg(feature = "rouille")]
 pub mod rouille {
     extern crate rouille;
-    use super::{As, DisplayAs, Format};
-    impl<'a, F: Format, T: DisplayAs<F>> Into<rouille::Response> for As<'a, F, T> {
-        fn into(self) -> rouille::Response {
-            let s = format!("{}", &self);
+    use super::{As, DisplayAs, Format, FormattedString, Negotiate};
+    #[doc(hidden)]
+    pub use self::rouille::Response;
+    impl<'a, F: Format, T: DisplayAs<F>> From<As<'a, F, T>> for rouille::Response {
+        fn from(x: As<'a, F, T>) -> Self {
+            let s = format!("{}", &x);
             rouille::Response::from_data(F::mime().as_ref().to_string(), s)
         }
     }
+    impl<F: Format> From<FormattedString<F>> for rouille::Response {
+        fn from(s: FormattedString<F>) -> Self {
+            rouille::Response::from_data(F::mime().as_ref().to_string(), s.inner)
+        }
+    }
+    /// The `Accept` header must be given to [Negotiate::new].
+    impl<T> From<Negotiate<T>> for rouille::Response {
+        fn from(n: Negotiate<T>) -> Self {
+            let response = match n.negotiate(None) {
+                Some((mime, s)) => rouille::Response::from_data(mime.as_ref().to_string(), s),
+                None => rouille::Response::empty_406(),
+            };
+            response.with_additional_header("Vary", "Accept")
+        }
+    }
+    impl<F: Format, T: DisplayAs<F>> From<crate::Response<F, T>> for rouille::Response {
+        fn from(r: crate::Response<F, T>) -> Self {
+            let (status, headers, body) = r.into_parts();
+            let mut response = rouille::Response::text(body).with_status_code(status);
+            response.headers = headers
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect();
+            response
+        }
+    }
+
+    #[test]
+    fn test_rouille_response() {
+        use crate::HTML;
+        use std::io::Read;
+
+        let response: rouille::Response = crate::Response::new(HTML, "a<b")
+            .status(410)
+            .header("Content-Type", "application/xhtml+xml")
+            .cache_control("no-store")
+            .header("X-Reason", "deleted")
+            .into();
+        assert_eq!(response.status_code, 410);
+        let headers: Vec<_> = response
+            .headers
+            .iter()
+            .map(|(k, v)| (k.as_ref(), v.as_ref()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                ("Content-Type", "application/xhtml+xml"),
+                ("Cache-Control", "no-store"),
+                ("X-Reason", "deleted"),
+            ]
+        );
+        let mut body = String::new();
+        let (mut reader, _) = response.data.into_reader_and_size();
+        reader.read_to_string(&mut body).unwrap();
+        assert_eq!(body, "a&lt;b");
+    }
+
+    #[test]
+    fn test_rouille_negotiate() {
+        use crate::{HTML, UTF8};
+
+        let negotiate = |accept| -> rouille::Response {
+            Negotiate::new("a<b", accept)
+                .format(HTML)
+                .format(UTF8)
+                .into()
+        };
+        let header = |r: &rouille::Response, name: &str| {
+            r.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.to_string())
+        };
+        let response = negotiate(Some("text/plain"));
+        assert_eq!(response.status_code, 200);
+        assert_eq!(
+            header(&response, "Content-Type").as_deref(),
+            Some(mime::TEXT_PLAIN_UTF_8.as_ref())
+        );
+        assert_eq!(header(&response, "Vary").as_deref(), Some("Accept"));
+
+        let response = negotiate(Some("image/png"));
+        assert_eq!(response.status_code, 406);
+        assert_eq!(header(&response, "Vary").as_deref(), Some("Accept"));
+    }
+
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! __rouille_responder {
+        ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+            impl<$($g)*> ::std::convert::From<$($t)*> for $crate::rouille::Response $($w)* {
+                fn from(x: $($t)*) -> Self {
+                    let s = format!("{}", $crate::As::<$f, _>::from(&x));
+                    $crate::FormattedString::<$f>::from_formatted(s).into()
+                }
+            }
+        };
+    }
 }
 
-/// The `actix-web` feature flag makes any [`As`] type a
-/// [actix_web::Responder].
+/// The `actix-web` feature flag makes any [`As`] type or
+/// [`FormattedString`] an [actix_web::Responder], for actix-web 4.
+///
+/// A [`Negotiate`] made with `Negotiate::new(x, None)` uses the
+/// `Accept` header of the request.
 #[cfg(feature = "actix-web")]
 pub mod actix {
-    extern crate actix_web;
-    use self::actix_web::{HttpRequest, HttpResponse, Responder};
-    use super::{As, DisplayAs, Format};
-    impl<'a, F: Format, T: 'a + DisplayAs<F>> Responder for As<'a, F, T> {
-        type Item = HttpResponse;
-        type Error = ::std::io::Error;
-        fn respond_to<S: 'static>(
-            self,
-            _req: &HttpRequest<S>,
-        ) -> Result<HttpResponse, Self::Error> {
-            Ok(HttpResponse::Ok()
-                .content_type(F::mime().as_ref().to_string())
-                .body(format!("{}", &self)))
+    use crate::{As, DisplayAs, Format, FormattedString, Negotiate};
+    #[doc(hidden)]
+    pub use actix_web::body::BoxBody;
+    use actix_web::http::header::{ACCEPT, VARY};
+    use actix_web::http::StatusCode;
+    #[doc(hidden)]
+    pub use actix_web::{HttpRequest, HttpResponse, Responder};
+
+    impl<'a, F: Format, T: DisplayAs<F> + ?Sized> Responder for As<'a, F, T> {
+        type Body = BoxBody;
+        fn respond_to(self, _req: &HttpRequest) -> HttpResponse<BoxBody> {
+            HttpResponse::Ok()
+                .content_type(F::mime().as_ref())
+                .body(format!("{}", self))
         }
     }
+
+    impl<F: Format> Responder for FormattedString<F> {
+        type Body = BoxBody;
+        fn respond_to(self, _req: &HttpRequest) -> HttpResponse<BoxBody> {
+            HttpResponse::Ok()
+                .content_type(F::mime().as_ref())
+                .body(self.inner)
+        }
+    }
+
+    impl<T> Responder for Negotiate<T> {
+        type Body = BoxBody;
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<BoxBody> {
+            let accept = req.headers().get(ACCEPT).and_then(|h| h.to_str().ok());
+            match self.negotiate(accept) {
+                Some((mime, s)) => HttpResponse::Ok()
+                    .content_type(mime.as_ref())
+                    .insert_header((VARY, "Accept"))
+                    .body(s),
+                None => HttpResponse::NotAcceptable()
+                    .insert_header((VARY, "Accept"))
+                    .finish(),
+            }
+        }
+    }
+
+    impl<F: Format, T: DisplayAs<F>> Responder for crate::Response<F, T> {
+        type Body = BoxBody;
+        fn respond_to(self, _req: &HttpRequest) -> HttpResponse<BoxBody> {
+            let (status, headers, body) = self.into_parts();
+            let status = StatusCode::from_u16(status).expect("a valid status code");
+            let mut response = HttpResponse::build(status);
+            for header in headers {
+                response.append_header(header);
+            }
+            response.body(body)
+        }
+    }
+
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! __actix_responder {
+        ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+            impl<$($g)*> $crate::actix::Responder for $($t)* $($w)* {
+                type Body = $crate::actix::BoxBody;
+                fn respond_to(
+                    self,
+                    req: &$crate::actix::HttpRequest,
+                ) -> $crate::actix::HttpResponse<$crate::actix::BoxBody> {
+                    let s = format!("{}", $crate::As::<$f, _>::from(&self));
+                    $crate::actix::Responder::respond_to(
+                        $crate::FormattedString::<$f>::from_formatted(s),
+                        req,
+                    )
+                }
+            }
+        };
+    }
+
+    #[actix_web::test]
+    async fn test_actix() {
+        use crate::{display, format_as, HTML};
+        use actix_web::http::header::CONTENT_TYPE;
+        use actix_web::{test, web, App};
+
+        let app = test::init_service(
+            App::new()
+                .route("/", web::get().to(|| async { display(HTML, &42) }))
+                .route(
+                    "/page",
+                    web::get().to(|| async {
+                        let text = "a & b";
+                        format_as!(HTML, "<p>" text "</p>")
+                    }),
+                ),
+        )
+        .await;
+
+        let request = test::TestRequest::get().uri("/").to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            mime::TEXT_HTML_UTF_8.as_ref()
+        );
+        assert_eq!(&test::read_body(response).await[..], b"42");
+
+        let request = test::TestRequest::get().uri("/page").to_request();
+        let body = test::call_and_read_body(&app, request).await;
+        assert_eq!(&body[..], b"<p>a &amp; b</p>");
+    }
+
+    #[actix_web::test]
+    async fn test_actix_negotiate() {
+        use crate::{HTML, UTF8};
+        use actix_web::http::header::{CONTENT_TYPE, VARY};
+        use actix_web::http::StatusCode;
+        use actix_web::{test, web, App};
+
+        let app = test::init_service(App::new().route(
+            "/",
+            web::get().to(|| async { Negotiate::new(3e5, None).format(HTML).format(UTF8) }),
+        ))
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/")
+            .insert_header((ACCEPT, "text/html;q=0.5, text/plain"))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            mime::TEXT_PLAIN_UTF_8.as_ref()
+        );
+        assert_eq!(response.headers().get(VARY).unwrap(), "Accept");
+        assert_eq!(&test::read_body(response).await[..], b"3e5");
+
+        let request = test::TestRequest::get()
+            .uri("/")
+            .insert_header((ACCEPT, "application/json"))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(response.headers().get(VARY).unwrap(), "Accept");
+    }
+
+    #[actix_web::test]
+    async fn test_actix_response() {
+        use crate::HTML;
+        use actix_web::http::header::CONTENT_TYPE;
+        use actix_web::test;
+
+        let request = test::TestRequest::get().to_http_request();
+        let response = crate::Response::new(HTML, 1)
+            .status(404)
+            .header("Content-Type", "application/xhtml+xml")
+            .etag()
+            .respond_to(&request);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let content_types: Vec<_> = response.headers().get_all(CONTENT_TYPE).collect();
+        assert_eq!(content_types, vec!["application/xhtml+xml"]);
+        assert!(response.headers().contains_key("etag"));
+    }
 }
 
 /// The `gotham-web` feature flag makes any [`As`] type a
 /// [::gotham::handler::IntoResponse].
+///
+/// A [`Negotiate`] made with `Negotiate::new(x, None)` uses the
+/// `Accept` header of the request.
 #[cfg(feature = "gotham")]
 pub mod gotham {
-    use crate::{As, DisplayAs, Format};
-    use gotham::{
+    use crate::{As, DisplayAs, Format, FormattedString, Negotiate};
+    use gotham::hyper::header::{HeaderValue, ACCEPT, VARY};
+    use gotham::hyper::{HeaderMap, StatusCode};
+    use gotham::state::FromState;
+    #[doc(hidden)]
+    pub use gotham::{
         handler::IntoResponse,
-        hyper::{Body, Response, StatusCode},
+        hyper::{Body, Response},
         state::State,
     };
 
@@ -406,26 +738,203 @@ pub mod gotham {
             (StatusCode::OK, F::mime(), s).into_response(state)
         }
     }
+
+    impl<F: Format> IntoResponse for FormattedString<F> {
+        fn into_response(self, state: &State) -> Response<Body> {
+            (StatusCode::OK, F::mime(), self.inner).into_response(state)
+        }
+    }
+
+    impl<T> IntoResponse for Negotiate<T> {
+        fn into_response(self, state: &State) -> Response<Body> {
+            let accept = HeaderMap::borrow_from(state)
+                .get(ACCEPT)
+                .and_then(|h| h.to_str().ok());
+            let mut response = match self.negotiate(accept) {
+                Some((mime, s)) => (StatusCode::OK, mime, s).into_response(state),
+                None => {
+                    let mut response = Response::new(Body::empty());
+                    *response.status_mut() = StatusCode::NOT_ACCEPTABLE;
+                    response
+                }
+            };
+            response
+                .headers_mut()
+                .insert(VARY, HeaderValue::from_static("Accept"));
+            response
+        }
+    }
+
+    impl<F: Format, T: DisplayAs<F>> IntoResponse for crate::Response<F, T> {
+        fn into_response(self, _state: &State) -> Response<Body> {
+            let (status, headers, body) = self.into_parts();
+            let mut response = Response::builder().status(status);
+            for (k, v) in headers {
+                response = response.header(k.as_str(), v.as_str());
+            }
+            response.body(body.into()).expect("a valid response")
+        }
+    }
+
+    #[cfg(test)]
+    fn negotiate(state: State) -> (State, Negotiate<&'static str>) {
+        use crate::{HTML, UTF8};
+        (state, Negotiate::new("a<b", None).format(HTML).format(UTF8))
+    }
+
+    #[cfg(test)]
+    fn gone(state: State) -> (State, crate::Response<crate::HTML, &'static str>) {
+        let response = crate::Response::new(crate::HTML, "a<b")
+            .status(410)
+            .header("Content-Type", "application/xhtml+xml")
+            .cache_control("no-store")
+            .header("X-Reason", "deleted");
+        (state, response)
+    }
+
+    #[test]
+    fn test_gotham_response() {
+        use gotham::hyper::header::{CACHE_CONTROL, CONTENT_TYPE};
+        use gotham::test::TestServer;
+
+        let server = TestServer::new(|| Ok(gone)).unwrap();
+        let response = server.client().get("http://localhost/").perform().unwrap();
+        assert_eq!(response.status(), StatusCode::GONE);
+        let content_types: Vec<_> = response.headers().get_all(CONTENT_TYPE).iter().collect();
+        assert_eq!(content_types, vec!["application/xhtml+xml"]);
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-store");
+        assert_eq!(response.headers()["x-reason"], "deleted");
+        assert_eq!(&response.read_body().unwrap()[..], b"a&lt;b");
+    }
+
+    #[test]
+    fn test_gotham_negotiate() {
+        use gotham::test::TestServer;
+
+        let server = TestServer::new(|| Ok(negotiate)).unwrap();
+        let response = server
+            .client()
+            .get("http://localhost/")
+            .with_header(ACCEPT, HeaderValue::from_static("text/plain"))
+            .perform()
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[VARY], "Accept");
+        assert_eq!(&response.read_body().unwrap()[..], b"a<b");
+
+        let response = server.client().get("http://localhost/").perform().unwrap();
+        assert_eq!(&response.read_body().unwrap()[..], b"a&lt;b");
+
+        let response = server
+            .client()
+            .get("http://localhost/")
+            .with_header(ACCEPT, HeaderValue::from_static("image/png"))
+            .perform()
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(response.headers()[VARY], "Accept");
+    }
+
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! __gotham_responder {
+        ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+            impl<$($g)*> $crate::gotham::IntoResponse for $($t)* $($w)* {
+                fn into_response(
+                    self,
+                    state: &$crate::gotham::State,
+                ) -> $crate::gotham::Response<$crate::gotham::Body> {
+                    let s = format!("{}", $crate::As::<$f, _>::from(&self));
+                    $crate::gotham::IntoResponse::into_response(
+                        $crate::FormattedString::<$f>::from_formatted(s),
+                        state,
+                    )
+                }
+            }
+        };
+    }
 }
 
 /// The `warp` feature flag makes any [`DisplayAs`] type a [warp::Reply].
+///
+/// A reply cannot see the request, so a [`Negotiate`] must be given
+/// its `Accept` header, for instance from
+/// `warp::header::optional("accept")`.  Otherwise any format is
+/// acceptable.
 #[cfg(feature = "warp")]
 pub mod warp {
-    use crate::{As, DisplayAs, Format};
+    use crate::{As, DisplayAs, Format, FormattedString, Negotiate};
+    #[doc(hidden)]
+    pub use warp::reply::{Reply, Response};
+
+    fn reply<F: Format>(s: String) -> warp::reply::Response {
+        reply_mime(F::mime(), s)
+    }
+
+    fn reply_mime(mime: mime::Mime, s: String) -> warp::reply::Response {
+        warp::http::Response::builder()
+            .header("Content-type", mime.as_ref().as_bytes())
+            .status(warp::http::StatusCode::OK)
+            .body(s)
+            .unwrap()
+            .map(warp::hyper::Body::from)
+    }
+
     impl<'a, F: Format, T: DisplayAs<F> + Sync> warp::Reply for As<'a, F, T> {
         /// Convert into a [warp::Reply].
         fn into_response(self) -> warp::reply::Response {
-            let s = format!("{}", self);
-            let m = F::mime().as_ref().to_string();
-            warp::http::Response::builder()
-                .header("Content-type", m.as_bytes())
-                .status(warp::http::StatusCode::OK)
-                .body(s)
-                .unwrap()
-                .map(warp::hyper::Body::from)
+            reply::<F>(format!("{}", self))
+        }
+    }
+
+    impl<F: Format> warp::Reply for FormattedString<F> {
+        fn into_response(self) -> warp::reply::Response {
+            reply::<F>(self.inner)
+        }
+    }
+
+    /// The `Accept` header must be given to [Negotiate::new], for
+    /// instance from `warp::header::optional("accept")`.
+    impl<T: Send> warp::Reply for Negotiate<T> {
+        fn into_response(self) -> warp::reply::Response {
+            let mut response = match self.negotiate(None) {
+                Some((mime, s)) => reply_mime(mime, s),
+                None => warp::http::StatusCode::NOT_ACCEPTABLE.into_response(),
+            };
+            response.headers_mut().insert(
+                warp::http::header::VARY,
+                warp::http::HeaderValue::from_static("Accept"),
+            );
+            response
+        }
+    }
+
+    impl<F: Format, T: DisplayAs<F> + Send> warp::Reply for crate::Response<F, T> {
+        fn into_response(self) -> warp::reply::Response {
+            let (status, headers, body) = self.into_parts();
+            let mut response = warp::http::Response::builder().status(status);
+            for (k, v) in headers {
+                response = response.header(k.as_str(), v.as_str());
+            }
+            response.body(body.into()).expect("a valid response")
         }
     }
 
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! __warp_responder {
+        ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+            impl<$($g)*> $crate::warp::Reply for $($t)* $($w)* {
+                fn into_response(self) -> $crate::warp::Response {
+                    let s = format!("{}", $crate::As::<$f, _>::from(&self));
+                    $crate::warp::Reply::into_response(
+                        $crate::FormattedString::<$f>::from_formatted(s),
+                    )
+                }
+            }
+        };
+    }
+
     #[test]
     fn test_warp() {
         use crate::{display, HTML};
@@ -433,6 +942,755 @@ pub mod warp {
         // This sloppy test just verify that the code runs.
         display(HTML, &"hello world".to_string()).into_response();
     }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_warp_response() {
+        use crate::HTML;
+        use warp::http::StatusCode;
+        use warp::Filter;
+
+        let route = warp::any().map(|| {
+            crate::Response::new(HTML, "a<b")
+                .status(410)
+                .header("Content-Type", "application/xhtml+xml")
+                .cache_control("no-store")
+                .header("X-Reason", "deleted")
+        });
+        let response = warp::test::request().reply(&route).await;
+        assert_eq!(response.status(), StatusCode::GONE);
+        let content_types: Vec<_> = response.headers().get_all("content-type").iter().collect();
+        assert_eq!(content_types, vec!["application/xhtml+xml"]);
+        assert_eq!(response.headers()["cache-control"], "no-store");
+        assert_eq!(response.headers()["x-reason"], "deleted");
+        assert_eq!(&response.body()[..], b"a&lt;b");
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_warp_negotiate() {
+        use crate::{HTML, UTF8};
+        use warp::http::StatusCode;
+        use warp::Filter;
+
+        let route = warp::header::optional("accept").map(|accept: Option<String>| {
+            Negotiate::new("a<b", accept.as_deref())
+                .format(HTML)
+                .format(UTF8)
+        });
+
+        let response = warp::test::request()
+            .header("accept", "text/plain")
+            .reply(&route)
+            .await;
+        assert_eq!(
+            response.headers()["content-type"],
+            mime::TEXT_PLAIN_UTF_8.as_ref()
+        );
+        assert_eq!(response.headers()["vary"], "Accept");
+        assert_eq!(&response.body()[..], b"a<b");
+
+        let response = warp::test::request().reply(&route).await;
+        assert_eq!(&response.body()[..], b"a&lt;b");
+
+        let response = warp::test::request()
+            .header("accept", "image/png")
+            .reply(&route)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(response.headers()["vary"], "Accept");
+    }
+}
+
+/// The `axum` feature flag makes any [`As`] type or [`FormattedString`]
+/// an [axum::response::IntoResponse], with a `Content-Type` given by
+/// its [`Format`].  Use axum's tuple responses to set a status code or
+/// headers, as in `(StatusCode::CREATED, display(HTML, &x))`, or a
+/// [`Response`](crate::Response) as with any other framework.
+///
+/// A response cannot see the request, so a [`Negotiate`] must be
+/// given its `Accept` header, for instance from a `HeaderMap`
+/// extracted by the handler.  Otherwise any format is acceptable.
+#[cfg(feature = "axum")]
+pub mod axum {
+    use crate::{As, DisplayAs, Format, FormattedString, Negotiate};
+    use axum::http::header::{CONTENT_TYPE, VARY};
+    use axum::http::StatusCode;
+    #[doc(hidden)]
+    pub use axum::response::{IntoResponse, Response};
+
+    impl<'a, F: Format, T: DisplayAs<F> + ?Sized> IntoResponse for As<'a, F, T> {
+        fn into_response(self) -> Response {
+            let s = format!("{}", self);
+            ([(CONTENT_TYPE, F::mime().as_ref().to_string())], s).into_response()
+        }
+    }
+
+    impl<F: Format> IntoResponse for FormattedString<F> {
+        fn into_response(self) -> Response {
+            ([(CONTENT_TYPE, F::mime().as_ref().to_string())], self.inner).into_response()
+        }
+    }
+
+    /// The `Accept` header must be given to [Negotiate::new], for
+    /// instance from a `HeaderMap` extracted by the handler.
+    impl<T> IntoResponse for Negotiate<T> {
+        fn into_response(self) -> Response {
+            let vary = [(VARY, "Accept")];
+            match self.negotiate(None) {
+                Some((mime, s)) => {
+                    (vary, [(CONTENT_TYPE, mime.as_ref().to_string())], s).into_response()
+                }
+                None => (StatusCode::NOT_ACCEPTABLE, vary).into_response(),
+            }
+        }
+    }
+
+    impl<F: Format, T: DisplayAs<F>> IntoResponse for crate::Response<F, T> {
+        fn into_response(self) -> Response {
+            let (status, headers, body) = self.into_parts();
+            let mut response = Response::builder().status(status);
+            for (k, v) in headers {
+                response = response.header(k, v);
+            }
+            response.body(body.into()).expect("a valid response")
+        }
+    }
+
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! __axum_responder {
+        ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+            impl<$($g)*> $crate::axum::IntoResponse for $($t)* $($w)* {
+                fn into_response(self) -> $crate::axum::Response {
+                    let s = format!("{}", $crate::As::<$f, _>::from(&self));
+                    $crate::axum::IntoResponse::into_response(
+                        $crate::FormattedString::<$f>::from_formatted(s),
+                    )
+                }
+            }
+        };
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_axum() {
+        use crate::{display, format_as, HTML};
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use axum::routing::get;
+        use axum::Router;
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    let text = "a & b";
+                    format_as!(HTML, "<p>" text "</p>")
+                }),
+            )
+            .route(
+                "/created",
+                get(|| async { (StatusCode::CREATED, display(HTML, &42)) }),
+            )
+            .route(
+                "/gone",
+                get(|| async {
+                    crate::Response::new(HTML, "a<b")
+                        .status(410)
+                        .cache_control("no-store")
+                        .header("X-Reason", "deleted")
+                }),
+            );
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            mime::TEXT_HTML_UTF_8.as_ref()
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"<p>a &amp; b</p>");
+
+        let request = Request::builder()
+            .uri("/created")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"42");
+
+        let request = Request::builder().uri("/gone").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::GONE);
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            mime::TEXT_HTML_UTF_8.as_ref()
+        );
+        assert_eq!(response.headers()["cache-control"], "no-store");
+        assert_eq!(response.headers()["x-reason"], "deleted");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"a&lt;b");
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_axum_negotiate() {
+        use crate::{HTML, UTF8};
+        use axum::body::{to_bytes, Body};
+        use axum::http::header::ACCEPT;
+        use axum::http::{HeaderMap, Request};
+        use axum::routing::get;
+        use axum::Router;
+        use tower::ServiceExt;
+
+        let app = Router::new().route(
+            "/",
+            get(|headers: HeaderMap| async move {
+                let accept = headers.get(ACCEPT).and_then(|h| h.to_str().ok());
+                Negotiate::new("a<b", accept).format(HTML).format(UTF8)
+            }),
+        );
+
+        let request = Request::builder()
+            .uri("/")
+            .header(ACCEPT, "text/*;q=0.5, text/plain")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            mime::TEXT_PLAIN_UTF_8.as_ref()
+        );
+        assert_eq!(response.headers()[VARY], "Accept");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"a<b");
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"a&lt;b");
+
+        let request = Request::builder()
+            .uri("/")
+            .header(ACCEPT, "image/png")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(response.headers()[VARY], "Accept");
+    }
+}
+
+/// The `rocket` feature flag makes any [`As`] type or
+/// [`FormattedString`] a [rocket::response::Responder].
+///
+/// A [`Negotiate`] made with `Negotiate::new(x, None)` uses the
+/// `Accept` header of the request.
+#[cfg(feature = "rocket")]
+pub mod rocket {
+    use crate::{As, DisplayAs, Format, FormattedString, Negotiate};
+    use rocket::http::{ContentType, Header, Status};
+    #[doc(hidden)]
+    pub use rocket::response::{self, Responder, Response};
+    #[doc(hidden)]
+    pub use rocket::Request;
+    use std::io::Cursor;
+
+    fn respond<'o, F: Format>(s: String) -> response::Result<'o> {
+        respond_mime(F::mime(), s)
+    }
+
+    fn respond_mime<'o>(mime: mime::Mime, s: String) -> response::Result<'o> {
+        let content_type =
+            ContentType::parse_flexible(mime.as_ref()).expect("every format has a valid MIME type");
+        Response::build()
+            .header(content_type)
+            .sized_body(s.len(), Cursor::new(s))
+            .ok()
+    }
+
+    impl<'r, 'o: 'r, 'a, F: Format, T: DisplayAs<F> + ?Sized> Responder<'r, 'o> for As<'a, F, T> {
+        fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
+            respond::<F>(format!("{}", self))
+        }
+    }
+
+    impl<'r, 'o: 'r, F: Format> Responder<'r, 'o> for FormattedString<F> {
+        fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
+            respond::<F>(self.inner)
+        }
+    }
+
+    impl<'r, 'o: 'r, T> Responder<'r, 'o> for Negotiate<T> {
+        fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+            let mut response = match self.negotiate(req.headers().get_one("Accept")) {
+                Some((mime, s)) => respond_mime(mime, s)?,
+                None => Response::build().status(Status::NotAcceptable).finalize(),
+            };
+            response.set_header(Header::new("Vary", "Accept"));
+            Ok(response)
+        }
+    }
+
+    impl<'r, 'o: 'r, F: Format, T: DisplayAs<F>> Responder<'r, 'o> for crate::Response<F, T> {
+        fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
+            let (status, headers, body) = self.into_parts();
+            let mut response = Response::build();
+            response.status(Status::new(status));
+            for (k, v) in headers {
+                response.raw_header_adjoin(k, v);
+            }
+            response.sized_body(body.len(), Cursor::new(body)).ok()
+        }
+    }
+
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! __rocket_responder {
+        ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+            impl<'r, 'o: 'r, $($g)*> $crate::rocket::Responder<'r, 'o> for $($t)* $($w)* {
+                fn respond_to(
+                    self,
+                    req: &'r $crate::rocket::Request<'_>,
+                ) -> $crate::rocket::response::Result<'o> {
+                    let s = format!("{}", $crate::As::<$f, _>::from(&self));
+                    $crate::rocket::Responder::respond_to(
+                        $crate::FormattedString::<$f>::from_formatted(s),
+                        req,
+                    )
+                }
+            }
+        };
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::{display, format_as, As, FormattedString, Negotiate, HTML, UTF8};
+        use rocket::http::{ContentType, Status};
+        use rocket::local::blocking::Client;
+
+        #[rocket::get("/")]
+        fn index() -> FormattedString<HTML> {
+            let text = "a & b";
+            format_as!(HTML, "<p>" text "</p>")
+        }
+        #[rocket::get("/number")]
+        fn number() -> As<'static, HTML, i32> {
+            display(HTML, &42)
+        }
+        #[rocket::get("/cached")]
+        fn cached() -> crate::Response<HTML, i32> {
+            crate::Response::new(HTML, 42)
+                .status(203)
+                .cache_control("max-age=60")
+                .etag()
+        }
+        #[rocket::get("/negotiate")]
+        fn negotiate() -> Negotiate<&'static str> {
+            Negotiate::new("a<b", None).format(HTML).format(UTF8)
+        }
+
+        #[test]
+        fn test_rocket() {
+            let rocket = rocket::build().mount("/", rocket::routes![index, number, cached]);
+            let client = Client::untracked(rocket).unwrap();
+            let response = client.get("/").dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.content_type(), Some(ContentType::HTML));
+            assert_eq!(response.into_string().unwrap(), "<p>a &amp; b</p>");
+            let response = client.get("/number").dispatch();
+            assert_eq!(response.into_string().unwrap(), "42");
+            let response = client.get("/cached").dispatch();
+            assert_eq!(response.status(), Status::NonAuthoritativeInformation);
+            assert_eq!(response.content_type(), Some(ContentType::HTML));
+            assert_eq!(
+                response.headers().get_one("Cache-Control"),
+                Some("max-age=60")
+            );
+            assert!(response.headers().get_one("ETag").unwrap().starts_with('"'));
+            assert_eq!(response.into_string().unwrap(), "42");
+        }
+
+        #[test]
+        fn test_rocket_negotiate() {
+            use rocket::http::{Accept, Header};
+            let rocket = rocket::build().mount("/", rocket::routes![negotiate]);
+            let client = Client::untracked(rocket).unwrap();
+            let response = client.get("/negotiate").header(Accept::Text).dispatch();
+            assert_eq!(response.content_type(), Some(ContentType::Text));
+            assert_eq!(response.headers().get_one("Vary"), Some("Accept"));
+            assert_eq!(response.into_string().unwrap(), "a<b");
+            let response = client.get("/negotiate").dispatch();
+            assert_eq!(response.content_type(), Some(ContentType::HTML));
+            assert_eq!(response.into_string().unwrap(), "a&lt;b");
+            let response = client
+                .get("/negotiate")
+                .header(Header::new("Accept", "image/png"))
+                .dispatch();
+            assert_eq!(response.status(), Status::NotAcceptable);
+            assert_eq!(response.headers().get_one("Vary"), Some("Accept"));
+        }
+    }
+}
+
+/// The `hyper1` feature flag converts a [`FormattedString`] into a
+/// [hyper::Response], with a `Content-Type` given by its [`Format`].
+///
+/// A response cannot see the request, so a [`Negotiate`] must be
+/// given its `Accept` header from `request.headers()`.  Otherwise any
+/// format is acceptable.
+#[cfg(feature = "hyper1")]
+pub mod hyper {
+    use crate::{DisplayAs, Format, FormattedString, Negotiate};
+    #[doc(hidden)]
+    pub use bytes::Bytes;
+    #[doc(hidden)]
+    pub use http_body_util::Full;
+    use hyper::header::{HeaderValue, CONTENT_TYPE, VARY};
+    #[doc(hidden)]
+    pub use hyper::Response;
+
+    fn respond(mime: mime::Mime, s: String) -> Response<Full<Bytes>> {
+        let mut response = Response::new(Full::new(Bytes::from(s)));
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            mime.as_ref()
+                .parse()
+                .expect("every format has a valid MIME type"),
+        );
+        response
+    }
+
+    impl<F: Format> From<FormattedString<F>> for Response<Full<Bytes>> {
+        fn from(s: FormattedString<F>) -> Self {
+            respond(F::mime(), s.inner)
+        }
+    }
+
+    /// The `Accept` header must be given to [Negotiate::new].
+    impl<T> From<Negotiate<T>> for Response<Full<Bytes>> {
+        fn from(n: Negotiate<T>) -> Self {
+            let mut response = match n.negotiate(None) {
+                Some((mime, s)) => respond(mime, s),
+                None => {
+                    let mut response = Response::new(Full::new(Bytes::new()));
+                    *response.status_mut() = hyper::StatusCode::NOT_ACCEPTABLE;
+                    response
+                }
+            };
+            response
+                .headers_mut()
+                .insert(VARY, HeaderValue::from_static("Accept"));
+            response
+        }
+    }
+
+    impl<F: Format, T: DisplayAs<F>> From<crate::Response<F, T>> for Response<Full<Bytes>> {
+        fn from(r: crate::Response<F, T>) -> Self {
+            let (status, headers, body) = r.into_parts();
+            let mut response = Response::builder().status(status);
+            for (k, v) in headers {
+                response = response.header(k, v);
+            }
+            response
+                .body(Full::new(Bytes::from(body)))
+                .expect("a valid response")
+        }
+    }
+
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! __hyper_responder {
+        ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+            impl<$($g)*> ::std::convert::From<$($t)*>
+                for $crate::hyper::Response<$crate::hyper::Full<$crate::hyper::Bytes>> $($w)*
+            {
+                fn from(x: $($t)*) -> Self {
+                    let s = format!("{}", $crate::As::<$f, _>::from(&x));
+                    $crate::FormattedString::<$f>::from_formatted(s).into()
+                }
+            }
+        };
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_hyper() {
+        use crate::{format_as, HTML};
+        use http_body_util::BodyExt;
+
+        let x = 3e5;
+        let response: Response<Full<Bytes>> = format_as!(HTML, "<b>" x "</b>").into();
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            mime::TEXT_HTML_UTF_8.as_ref()
+        );
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], "<b>3×10<sup>5</sup></b>".as_bytes());
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_hyper_response() {
+        use crate::HTML;
+        use http_body_util::BodyExt;
+
+        let response: Response<Full<Bytes>> = crate::Response::new(HTML, "a<b")
+            .status(410)
+            .header("Content-Type", "application/xhtml+xml")
+            .cache_control("no-store")
+            .header("X-Reason", "deleted")
+            .into();
+        assert_eq!(response.status(), hyper::StatusCode::GONE);
+        let content_types: Vec<_> = response.headers().get_all(CONTENT_TYPE).iter().collect();
+        assert_eq!(content_types, vec!["application/xhtml+xml"]);
+        assert_eq!(response.headers()["cache-control"], "no-store");
+        assert_eq!(response.headers()["x-reason"], "deleted");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"a&lt;b");
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_hyper_negotiate() {
+        use crate::{HTML, UTF8};
+        use http_body_util::BodyExt;
+
+        let negotiate = |accept| Negotiate::new("a<b", accept).format(HTML).format(UTF8);
+        let response: Response<Full<Bytes>> = negotiate(Some("text/plain")).into();
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            mime::TEXT_PLAIN_UTF_8.as_ref()
+        );
+        assert_eq!(response.headers()[VARY], "Accept");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"a<b");
+
+        let response: Response<Full<Bytes>> = negotiate(None).into();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"a&lt;b");
+
+        let response: Response<Full<Bytes>> = negotiate(Some("image/png")).into();
+        assert_eq!(response.status(), hyper::StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(response.headers()[VARY], "Accept");
+    }
+}
+
+/// The `poem` feature flag makes any [`As`] type or
+/// [`FormattedString`] a [poem::IntoResponse].
+///
+/// A response cannot see the request, so a [`Negotiate`] must be
+/// given its `Accept` header, as in `Negotiate::new(x,
+/// request.header("Accept"))`.  Otherwise any format is acceptable.
+#[cfg(feature = "poem")]
+pub mod poem {
+    use crate::{As, DisplayAs, Format, FormattedString, Negotiate};
+    use poem::http::header::{CONTENT_TYPE, VARY};
+    use poem::http::StatusCode;
+    #[doc(hidden)]
+    pub use poem::{IntoResponse, Response};
+
+    impl<'a, F: Format, T: DisplayAs<F> + Sync + ?Sized> IntoResponse for As<'a, F, T> {
+        fn into_response(self) -> Response {
+            Response::builder()
+                .header(CONTENT_TYPE, F::mime().as_ref())
+                .body(format!("{}", self))
+        }
+    }
+
+    impl<F: Format> IntoResponse for FormattedString<F> {
+        fn into_response(self) -> Response {
+            Response::builder()
+                .header(CONTENT_TYPE, F::mime().as_ref())
+                .body(self.inner)
+        }
+    }
+
+    /// The `Accept` header must be given to [Negotiate::new].
+    impl<T: Send> IntoResponse for Negotiate<T> {
+        fn into_response(self) -> Response {
+            let response = Response::builder().header(VARY, "Accept");
+            match self.negotiate(None) {
+                Some((mime, s)) => response.header(CONTENT_TYPE, mime.as_ref()).body(s),
+                None => response.status(StatusCode::NOT_ACCEPTABLE).finish(),
+            }
+        }
+    }
+
+    impl<F: Format, T: DisplayAs<F> + Send> IntoResponse for crate::Response<F, T> {
+        fn into_response(self) -> Response {
+            let (status, headers, body) = self.into_parts();
+            let mut response = Response::builder()
+                .status(StatusCode::from_u16(status).expect("a valid status code"));
+            for (k, v) in headers {
+                response = response.header(k, v);
+            }
+            response.body(body)
+        }
+    }
+
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! __poem_responder {
+        ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+            impl<$($g)*> $crate::poem::IntoResponse for $($t)* $($w)* {
+                fn into_response(self) -> $crate::poem::Response {
+                    let s = format!("{}", $crate::As::<$f, _>::from(&self));
+                    $crate::poem::IntoResponse::into_response(
+                        $crate::FormattedString::<$f>::from_formatted(s),
+                    )
+                }
+            }
+        };
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_poem() {
+        use crate::{display, HTML};
+        use poem::{get, handler, Endpoint, Request, Route};
+
+        #[handler]
+        fn index() -> As<'static, HTML, &'static str> {
+            display(HTML, &"a<b")
+        }
+
+        let app = Route::new().at("/", get(index));
+        let response = app.get_response(Request::builder().finish()).await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            mime::TEXT_HTML_UTF_8.as_ref()
+        );
+        assert_eq!(response.into_body().into_string().await.unwrap(), "a&lt;b");
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_poem_negotiate() {
+        use crate::{HTML, UTF8};
+        use poem::{get, handler, Endpoint, Request, Route};
+
+        #[handler]
+        fn index(request: &Request) -> Negotiate<&'static str> {
+            Negotiate::new("a<b", request.header("Accept"))
+                .format(HTML)
+                .format(UTF8)
+        }
+
+        let app = Route::new().at("/", get(index));
+        let request = Request::builder().header("Accept", "text/plain").finish();
+        let response = app.get_response(request).await;
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            mime::TEXT_PLAIN_UTF_8.as_ref()
+        );
+        assert_eq!(response.headers()[VARY], "Accept");
+        assert_eq!(response.into_body().into_string().await.unwrap(), "a<b");
+
+        let request = Request::builder().header("Accept", "image/png").finish();
+        let response = app.get_response(request).await;
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(response.headers()[VARY], "Accept");
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn test_poem_response() {
+        use crate::HTML;
+
+        let response = crate::Response::new(HTML, "a<b")
+            .status(410)
+            .header("Content-Type", "application/xhtml+xml")
+            .cache_control("no-store")
+            .header("X-Reason", "deleted")
+            .into_response();
+        assert_eq!(response.status(), StatusCode::GONE);
+        let content_types: Vec<_> = response.headers().get_all(CONTENT_TYPE).iter().collect();
+        assert_eq!(content_types, vec!["application/xhtml+xml"]);
+        assert_eq!(response.headers()["cache-control"], "no-store");
+        assert_eq!(response.headers()["x-reason"], "deleted");
+        assert_eq!(response.into_body().into_string().await.unwrap(), "a&lt;b");
+    }
+}
+
+/// Implemented for each type that [`with_response_template`] has made
+/// a response, so that using it on a second impl for the same type
+/// fails with an error that says so, whatever features are enabled.
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub trait with_response_template_may_only_be_used_once_per_type {}
+
+/// Implement the responder traits of every enabled web framework for
+/// a type, by displaying it in the given [`Format`].  This is used by
+/// [`with_response_template`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_responders {
+    ([$($g:tt)*] [$f:ty] [$($t:tt)*] [$($w:tt)*]) => {
+        impl<$($g)*> $crate::with_response_template_may_only_be_used_once_per_type
+            for $($t)* $($w)* {}
+        $crate::__rouille_responder!([$($g)*] [$f] [$($t)*] [$($w)*]);
+        $crate::__actix_responder!([$($g)*] [$f] [$($t)*] [$($w)*]);
+        $crate::__gotham_responder!([$($g)*] [$f] [$($t)*] [$($w)*]);
+        $crate::__warp_responder!([$($g)*] [$f] [$($t)*] [$($w)*]);
+        $crate::__axum_responder!([$($g)*] [$f] [$($t)*] [$($w)*]);
+        $crate::__rocket_responder!([$($g)*] [$f] [$($t)*] [$($w)*]);
+        $crate::__hyper_responder!([$($g)*] [$f] [$($t)*] [$($w)*]);
+        $crate::__poem_responder!([$($g)*] [$f] [$($t)*] [$($w)*]);
+    };
+}
+#[cfg(not(feature = "rouille"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rouille_responder {
+    ($($args:tt)*) => {};
+}
+#[cfg(not(feature = "actix-web"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __actix_responder {
+    ($($args:tt)*) => {};
+}
+#[cfg(not(feature = "gotham"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __gotham_responder {
+    ($($args:tt)*) => {};
+}
+#[cfg(not(feature = "warp"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __warp_responder {
+    ($($args:tt)*) => {};
+}
+#[cfg(not(feature = "axum"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __axum_responder {
+    ($($args:tt)*) => {};
+}
+#[cfg(not(feature = "rocket"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rocket_responder {
+    ($($args:tt)*) => {};
+}
+#[cfg(not(feature = "hyper1"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hyper_responder {
+    ($($args:tt)*) => {};
+}
+#[cfg(not(feature = "poem"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __poem_responder {
+    ($($args:tt)*) => {};
 }
 
 impl<F: Format> DisplayAs<F> for String {
@@ -440,22 +1698,162 @@ impl<F: Format> DisplayAs<F> for String {
         F::escape(f, self)
     }
 }
-impl<'a, F: Format> DisplayAs<F> for &'a String {
+impl<F: Format> DisplayAs<F> for str {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         F::escape(f, self)
     }
 }
-impl<F: Format> DisplayAs<F> for str {
+/// A reference is displayed just like the value it refers to.  This
+/// replaces the separate impls for `&str` and `&String`, and lets
+/// adapters such as [`join`] display the items of a borrowed
+/// collection.  It means that you cannot implement [`DisplayAs`] for
+/// `&YourType` separately from `YourType`.
+impl<F: Format, T: DisplayAs<F> + ?Sized> DisplayAs<F> for &T {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        F::escape(f, self)
+        (**self).fmt(f)
     }
+    fn estimate_size(&self) -> usize {
+        (**self).estimate_size()
+    }
+}
+
+macro_rules! display_as_through_pointer {
+    ($pointer:ident) => {
+        impl<F: Format, T: DisplayAs<F> + ?Sized> DisplayAs<F> for $pointer<T> {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                (**self).fmt(f)
+            }
+            fn estimate_size(&self) -> usize {
+                (**self).estimate_size()
+            }
+        }
+    };
 }
-impl<'a, F: Format> DisplayAs<F> for &'a str {
+display_as_through_pointer!(Box);
+display_as_through_pointer!(Rc);
+display_as_through_pointer!(Arc);
+
+impl<'a, F: Format, B: DisplayAs<F> + ToOwned + ?Sized> DisplayAs<F> for Cow<'a, B> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        F::escape(f, self)
+        (**self).fmt(f)
+    }
+    fn estimate_size(&self) -> usize {
+        (**self).estimate_size()
     }
 }
 
+/// An [`Option`] is displayed as its contents, or as nothing at all
+/// if it is `None`.  To display something else in place of `None`,
+/// use [`display_or`].
+///
+/// ```
+/// use display_as::{HTML, format_as};
+/// let nickname: Option<&str> = None;
+/// assert_eq!(&format_as!(HTML, "[" Some("Joe") "] [" nickname "]").into_string(),
+///            "[Joe] []");
+/// ```
+impl<F: Format, T: DisplayAs<F>> DisplayAs<F> for Option<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if let Some(x) = self {
+            x.fmt(f)
+        } else {
+            Ok(())
+        }
+    }
+    fn estimate_size(&self) -> usize {
+        self.as_ref().map(|x| x.estimate_size()).unwrap_or(0)
+    }
+}
+
+/// A [`Result`] is displayed as either its `Ok` value or its `Err`
+/// value.  To display something else in place of an error, use
+/// [`display_or`].
+impl<F: Format, T: DisplayAs<F>, E: DisplayAs<F>> DisplayAs<F> for Result<T, E> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            Ok(x) => x.fmt(f),
+            Err(e) => e.fmt(f),
+        }
+    }
+    fn estimate_size(&self) -> usize {
+        match self {
+            Ok(x) => x.estimate_size(),
+            Err(e) => e.estimate_size(),
+        }
+    }
+}
+
+/// Display an [`Option`] or [`Result`] with a fallback.
+///
+/// This is created by [`display_or`].
+pub struct Or<'a, T: ?Sized, D> {
+    inner: &'a T,
+    otherwise: D,
+}
+
+/// Display an [`Option`] (or [`Result`]), showing `otherwise` if it
+/// is `None` (or `Err`).
+///
+/// ```
+/// use display_as::{HTML, display_or, format_as};
+/// let email: Option<String> = None;
+/// assert_eq!(&format_as!(HTML, "Email: " display_or(&email, "<none>")).into_string(),
+///            "Email: &lt;none&gt;");
+/// let age: Result<usize, String> = Err("unknown".to_string());
+/// assert_eq!(&format_as!(HTML, "Age: " display_or(&age, 0)).into_string(),
+///            "Age: 0");
+/// ```
+pub fn display_or<T: ?Sized, D>(x: &T, otherwise: D) -> Or<'_, T, D> {
+    Or {
+        inner: x,
+        otherwise,
+    }
+}
+
+impl<'a, F: Format, T: DisplayAs<F>, D: DisplayAs<F>> DisplayAs<F> for Or<'a, Option<T>, D> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self.inner {
+            Some(x) => x.fmt(f),
+            None => self.otherwise.fmt(f),
+        }
+    }
+}
+
+impl<'a, F: Format, T: DisplayAs<F>, E, D: DisplayAs<F>> DisplayAs<F> for Or<'a, Result<T, E>, D> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self.inner {
+            Ok(x) => x.fmt(f),
+            Err(_) => self.otherwise.fmt(f),
+        }
+    }
+}
+
+#[test]
+fn pointers() {
+    assert_eq!(&format_as!(HTML, Box::new(1)).into_string(), "1");
+    assert_eq!(&format_as!(HTML, Rc::new("<")).into_string(), "&lt;");
+    assert_eq!(&format_as!(HTML, Arc::new(3e5)).into_string(), "3×10<sup>5</sup>");
+    let b: Box<str> = "&".into();
+    assert_eq!(&format_as!(HTML, b).into_string(), "&amp;");
+    let c: Cow<str> = Cow::Borrowed("a<b");
+    assert_eq!(&format_as!(HTML, c).into_string(), "a&lt;b");
+    let c: Cow<str> = Cow::Owned("a>b".to_string());
+    assert_eq!(&format_as!(LaTeX, c).into_string(), r"a\textgreater{}b");
+}
+
+#[test]
+fn options_and_results() {
+    let x: Option<usize> = None;
+    assert_eq!(&format_as!(HTML, x).into_string(), "");
+    assert_eq!(&format_as!(HTML, Some(5)).into_string(), "5");
+    assert_eq!(&format_as!(HTML, display_or(&x, "none")).into_string(), "none");
+    let r: Result<usize, &str> = Err("bad & wrong");
+    assert_eq!(&format_as!(HTML, r).into_string(), "bad &amp; wrong");
+    assert_eq!(&format_as!(HTML, display_or(&r, 0)).into_string(), "0");
+    let r: Result<usize, &str> = Ok(7);
+    assert_eq!(&format_as!(HTML, display_or(&r, 0)).into_string(), "7");
+}
+
 #[cfg(test)]
 mod tests {
     use super::{format_as, HTML};
@@ -0,0 +1,53 @@
+//! [Format] as Markdown
+
+use super::*;
+
+/// [Format] as Markdown.
+///
+/// Escaping uses backslashes, so text will not be interpreted as
+/// emphasis, links, headers, table delimiters or inline HTML.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Markdown;
+impl Format for Markdown {
+    fn escape(f: &mut Formatter, mut s: &str) -> Result<(), Error> {
+        let badstuff = "\\`*_[]<>#|~&";
+        while let Some(idx) = s.find(|c| badstuff.contains(c)) {
+            let (first, rest) = s.split_at(idx);
+            let (badchar, tail) = rest.split_at(1);
+            f.write_str(first)?;
+            f.write_str("\\")?;
+            f.write_str(badchar)?;
+            s = tail;
+        }
+        f.write_str(s)
+    }
+    fn mime() -> mime::Mime {
+        "text/markdown; charset=utf-8".parse().unwrap()
+    }
+    fn this_format() -> Self {
+        Markdown
+    }
+    fn infinity(f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("∞")
+    }
+}
+
+display_integers_as!(Markdown);
+display_bools_and_chars_as!(Markdown);
+display_floats_as!(Markdown, "e", "", 1, None);
+display_localized_as!(Markdown, "e", "", 1, None);
+display_humanized_as!(Markdown, " ", "", " ", crate::float::PrefixStyle::Unicode);
+
+#[test]
+fn escaping() {
+    assert_eq!(&format_as!(Markdown, ("&")).into_string(), r"\&");
+    assert_eq!(
+        &format_as!(Markdown, ("*hello* | [world]")).into_string(),
+        r"\*hello\* \| \[world\]"
+    );
+}
+#[test]
+fn floats() {
+    assert_eq!(&format_as!(Markdown, 3.0).into_string(), "3");
+    assert_eq!(&format_as!(Markdown, 3e5).into_string(), "3e5");
+}
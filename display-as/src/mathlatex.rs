@@ -7,38 +7,75 @@ use super::*;
 pub struct Math;
 impl Format for Math {
     fn mime() -> mime::Mime {
-        return "text/x-latex".parse().unwrap();
+        "text/x-latex".parse().unwrap()
     }
     fn this_format() -> Self {
         Math
     }
+    fn infinity(f: &mut Formatter) -> Result<(), Error> {
+        f.write_str(r"\infty")
+    }
+    /// Characters that are special in LaTeX are escaped using
+    /// commands that are valid in math mode, such as `\backslash` and
+    /// `\sim`.  A non-breaking space is written as `~`.
     fn escape(f: &mut Formatter, mut s: &str) -> Result<(), Error> {
-        let badstuff = "&{}#%\\~$_^";
+        let badstuff = "&{}#%\\~$_^\u{A0}";
         while let Some(idx) = s.find(|c| badstuff.contains(c)) {
             let (first, rest) = s.split_at(idx);
-            let (badchar, tail) = rest.split_at(1);
+            let badchar = rest.chars().next().unwrap();
+            let tail = &rest[badchar.len_utf8()..];
             f.write_str(first)?;
             f.write_str(match badchar {
-                "&" => r"\&",
-                "{" => r"\{",
-                "}" => r"\}",
-                "#" => r"\#",
-                "%" => r"\%",
-                "\\" => r"\textbackslash{}",
-                "~" => r"\textasciitilde{}",
-                "$" => r"\$",
-                "_" => r"\_",
-                "^" => r"\^",
+                '&' => r"\&",
+                '{' => r"\{",
+                '}' => r"\}",
+                '#' => r"\#",
+                '%' => r"\%",
+                '\\' => r"\backslash ",
+                '~' => r"\sim ",
+                '$' => r"\$",
+                '_' => r"\_",
+                '^' => r"\hat{}",
+                '\u{A0}' => "~",
                 _ => unreachable!(),
             })?;
             s = tail;
         }
         f.write_str(s)
     }
+    /// A comma is written as `{,}` so that LaTeX does not add space
+    /// after it, and a thin space as `\,`.
+    fn digit_separator(f: &mut Formatter, c: char) -> Result<(), Error> {
+        match c {
+            ',' => f.write_str("{,}"),
+            '\u{202F}' | '\u{2009}' => f.write_str(r"\,"),
+            '\'' => f.write_str(r"\text{'}"),
+            _ => Self::escape(f, c.encode_utf8(&mut [0; 4])),
+        }
+    }
 }
 
 display_integers_as!(Math);
+display_bools_and_chars_as!(Math);
 display_floats_as!(Math, r"\times10^{", "}", 3, Some("10^{"));
+display_localized_as!(Math, r"\times10^{", "}", 3, Some("10^{"));
+display_measurements_as!(
+    Math,
+    r"\times10^{",
+    "}",
+    3,
+    r" \pm ",
+    r"\,\mathrm{",
+    "}",
+    crate::float::PrefixStyle::Math
+);
+display_humanized_as!(
+    Math,
+    r"\,\mathrm{",
+    "}",
+    r"\;",
+    crate::float::PrefixStyle::Math
+);
 
 #[test]
 fn escaping() {
@@ -53,9 +90,66 @@ fn escaping() {
     );
 }
 #[test]
+fn reference_table() {
+    let table = [
+        ("&", r"\&"),
+        ("{", r"\{"),
+        ("}", r"\}"),
+        ("#", r"\#"),
+        ("%", r"\%"),
+        ("a\\b", r"a\backslash b"),
+        ("~x", r"\sim x"),
+        ("$", r"\$"),
+        ("_", r"\_"),
+        ("^", r"\hat{}"),
+        ("<|>", "<|>"),
+        ("a\u{A0}b", "a~b"),
+    ];
+    for (input, math) in table.iter() {
+        assert_eq!(&format_as!(Math, input).into_string(), math);
+    }
+}
+#[test]
 fn floats() {
     assert_eq!(&format_as!(Math, 3.0).into_string(), "3");
     assert_eq!(&format_as!(Math, 3e5).into_string(), r"3\times10^{5}");
     assert_eq!(&format_as!(Math, 1e5).into_string(), r"10^{5}");
     assert_eq!(&format_as!(Math, 3e4).into_string(), "30000");
+    assert_eq!(&format_as!(Math, -f64::INFINITY).into_string(), r"-\infty");
+    assert_eq!(
+        &format_as!(Math, crate::float::SigFigs(2.5e-8, 2)).into_string(),
+        r"2.5\times10^{-8}"
+    );
+}
+#[test]
+fn measurements() {
+    use crate::float::{Measured, Quantity};
+    assert_eq!(
+        &format_as!(Math, Measured::new(123456., 4000.)).into_string(),
+        r"1.23(4)\times10^{5}"
+    );
+    assert_eq!(
+        &format_as!(
+            Math,
+            Quantity::new(Measured::new(1.2345, 0.04).plus_minus(), "m/s")
+        )
+        .into_string(),
+        r"(1.23 \pm 0.04)\,\mathrm{m/s}"
+    );
+}
+#[test]
+fn localized() {
+    use crate::float::{Locale, Localized};
+    assert_eq!(
+        &format_as!(Math, Localized::new(1234.5, Locale::DE)).into_string(),
+        "1.234{,}5"
+    );
+    assert_eq!(
+        &format_as!(Math, Localized::new(12345, Locale::SI)).into_string(),
+        r"12\,345"
+    );
+    assert_eq!(
+        &format_as!(Math, Localized::new(1.5e10, Locale::DE)).into_string(),
+        r"1{,}5\times10^{10}"
+    );
 }
@@ -0,0 +1,209 @@
+//! Format as MathML
+
+use super::*;
+use crate::float::{Decimals, Engineering, Floating, SigFigs};
+
+/// Format as MathML, which browsers render natively.
+///
+/// Numbers are written as complete MathML elements, such as
+/// `<mn>42</mn>`, while strings are escaped so that they can be
+/// placed within an `<mi>` or `<mtext>` element.  MathML can be
+/// embedded directly in [HTML]:
+///
+/// ```
+/// use display_as::{HTML, MathML, format_as};
+/// assert_eq!(&format_as!(HTML, "<math>" 1.2e12 as MathML "</math>").into_string(),
+///            "<math><mrow><mn>1.2</mn><mo>×</mo><msup><mn>10</mn><mn>12</mn></msup></mrow></math>");
+/// let name = "x<y";
+/// assert_eq!(&format_as!(HTML, "<math><mi>" name as MathML "</mi></math>").into_string(),
+///            "<math><mi>x&lt;y</mi></math>");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MathML;
+impl Format for MathML {
+    fn escape(f: &mut Formatter, s: &str) -> Result<(), Error> {
+        HTML::escape(f, s)
+    }
+    fn mime() -> mime::Mime {
+        "application/mathml+xml".parse().unwrap()
+    }
+    fn this_format() -> Self {
+        MathML
+    }
+    fn infinity(f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("∞")
+    }
+}
+
+/// Write a minus sign, which is U+2212 rather than a hyphen.
+fn write_minus(f: &mut Formatter) -> Result<(), Error> {
+    f.write_str("<mo>−</mo>")
+}
+
+/// Write an exponent, which may be negative, as MathML elements.
+fn write_exponent(f: &mut Formatter, exponent: &str) -> Result<(), Error> {
+    match exponent.strip_prefix('-') {
+        Some(exponent) => {
+            f.write_str("<mrow>")?;
+            write_minus(f)?;
+            write!(f, "<mn>{}</mn></mrow>", exponent)
+        }
+        None => write!(f, "<mn>{}</mn>", exponent),
+    }
+}
+
+/// Write a number that has been formatted as [UTF8] with a plain `e`
+/// before any exponent as MathML elements.
+pub(crate) fn write_number(f: &mut Formatter, number: &str) -> Result<(), Error> {
+    let (negative, number) = match number.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, number),
+    };
+    let (mantissa, exponent) = match number.find('e') {
+        Some(idx) => (&number[..idx], Some(&number[idx + 1..])),
+        None => (number, None),
+    };
+    let is_row = negative || (exponent.is_some() && mantissa != "1");
+    if is_row {
+        f.write_str("<mrow>")?;
+    }
+    if negative {
+        write_minus(f)?;
+    }
+    match (mantissa, exponent) {
+        ("∞", _) => f.write_str("<mi>∞</mi>")?,
+        ("NaN", _) => f.write_str("<mi>NaN</mi>")?,
+        (_, None) => write!(f, "<mn>{}</mn>", mantissa)?,
+        (_, Some(exponent)) => {
+            if mantissa != "1" {
+                write!(f, "<mn>{}</mn><mo>×</mo>", mantissa)?;
+            }
+            f.write_str("<msup><mn>10</mn>")?;
+            write_exponent(f, exponent)?;
+            f.write_str("</msup>")?;
+        }
+    }
+    if is_row {
+        f.write_str("</mrow>")?;
+    }
+    Ok(())
+}
+
+/// Write a [Floating] as MathML, formatting it with `fmt` and
+/// respecting any precision of `f`.
+fn write_floating(
+    f: &mut Formatter,
+    fmt: impl Fn(&mut Formatter) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let closure = display_closure_as(UTF8, fmt);
+    let text = match f.precision() {
+        Some(precision) => format!("{:.*}", precision, closure.display()),
+        None => format!("{}", closure.display()),
+    };
+    write_number(f, &text)
+}
+
+macro_rules! display_integers_as_mathml {
+    ($($type:ty),*) => {
+        $(
+            impl DisplayAs<MathML> for $type {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    write_number(f, &self.to_string())
+                }
+            }
+        )*
+    };
+}
+display_integers_as_mathml!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+display_bools_and_chars_as!(MathML);
+display_humanized_as!(MathML);
+
+impl DisplayAs<MathML> for f64 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let x = Floating::from(*self);
+        write_floating(f, |f| x.fmt_as::<UTF8>(f, "e", "", 3, None))
+    }
+}
+impl DisplayAs<MathML> for f32 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let x = Floating::from(*self);
+        write_floating(f, |f| x.fmt_as::<UTF8>(f, "e", "", 3, None))
+    }
+}
+impl DisplayAs<MathML> for SigFigs {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let x = Floating::with_sig_figs(self.0, self.1);
+        write_floating(f, |f| x.fmt_as::<UTF8>(f, "e", "", 3, None))
+    }
+}
+impl DisplayAs<MathML> for Decimals {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let x = Floating::with_decimals(self.0, self.1);
+        write_floating(f, |f| x.fmt_positional_as::<UTF8>(f))
+    }
+}
+impl DisplayAs<MathML> for Engineering {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let x = self.floating();
+        write_floating(f, |f| x.fmt_engineering_as::<UTF8>(f, "e", ""))
+    }
+}
+
+#[test]
+fn escaping() {
+    assert_eq!(
+        &format_as!(MathML, ("a<b & c")).into_string(),
+        "a&lt;b &amp; c"
+    );
+}
+#[test]
+fn integers() {
+    assert_eq!(&format_as!(MathML, 42).into_string(), "<mn>42</mn>");
+    assert_eq!(
+        &format_as!(MathML, -7i8).into_string(),
+        "<mrow><mo>−</mo><mn>7</mn></mrow>"
+    );
+}
+#[test]
+fn floats() {
+    assert_eq!(&format_as!(MathML, 3.0).into_string(), "<mn>3</mn>");
+    assert_eq!(&format_as!(MathML, 0.25).into_string(), "<mn>0.25</mn>");
+    assert_eq!(
+        &format_as!(MathML, 1e5).into_string(),
+        "<msup><mn>10</mn><mn>5</mn></msup>"
+    );
+    assert_eq!(
+        &format_as!(MathML, -2.5e-8).into_string(),
+        "<mrow><mo>−</mo><mn>2.5</mn><mo>×</mo><msup><mn>10</mn><mrow><mo>−</mo><mn>8</mn></mrow></msup></mrow>"
+    );
+    assert_eq!(
+        &format_as!(MathML, 1e-7).into_string(),
+        "<msup><mn>10</mn><mrow><mo>−</mo><mn>7</mn></mrow></msup>"
+    );
+    assert_eq!(
+        &format_as!(MathML, f64::NEG_INFINITY).into_string(),
+        "<mrow><mo>−</mo><mi>∞</mi></mrow>"
+    );
+    assert_eq!(&format_as!(MathML, f32::NAN).into_string(), "<mi>NaN</mi>");
+    assert_eq!(
+        &format_as!(MathML, Decimals(1234567.891, 2)).into_string(),
+        "<mn>1234567.89</mn>"
+    );
+    assert_eq!(
+        &format_as!(MathML, Engineering::new(45e3)).into_string(),
+        "<mrow><mn>45</mn><mo>×</mo><msup><mn>10</mn><mn>3</mn></msup></mrow>"
+    );
+}
+#[test]
+fn text() {
+    assert_eq!(&format_as!(MathML, true).into_string(), "true");
+    assert_eq!(&format_as!(MathML, '<').into_string(), "&lt;");
+    assert_eq!(
+        &format_as!(MathML, std::path::Path::new("a<b")).into_string(),
+        "a&lt;b"
+    );
+    assert_eq!(
+        &format_as!(MathML, std::time::Duration::from_secs(61)).into_string(),
+        "1 min 1 s"
+    );
+}
@@ -0,0 +1,193 @@
+//! Matrices and vectors that can be displayed in several formats.
+
+use super::*;
+
+/// A matrix of values, which may be displayed as a `pmatrix` in
+/// [`Math`], a `<table class="matrix">` in [`HTML`], an `<mtable>`
+/// in [`MathML`], or aligned text in [`UTF8`].
+///
+/// Each element is displayed in the same format as the matrix, so
+/// floats use that format's usual notation.
+///
+/// ```
+/// use display_as::{Math, Matrix, UTF8, format_as};
+/// let m = Matrix::new(vec![vec![1.0, 2.5], vec![-3.0, 4e8]]);
+/// assert_eq!(&format_as!(Math, m).into_string(),
+///            r"\begin{pmatrix}1 & 2.5 \\ -3 & 4\times10^{8}\end{pmatrix}");
+/// assert_eq!(&format_as!(UTF8, m).into_string(),
+///            "⎛ 1  2.5⎞\n⎝-3  4e8⎠");
+/// assert_eq!(&format_as!(UTF8, Matrix::column(vec![1, 20])).into_string(),
+///            "⎛ 1⎞\n⎝20⎠");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Matrix<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T> Matrix<T> {
+    /// Create a matrix from its rows.
+    pub fn new<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>) -> Self {
+        Matrix {
+            rows: rows.into_iter().map(|r| r.into_iter().collect()).collect(),
+        }
+    }
+    /// Create a column vector.
+    pub fn column(values: impl IntoIterator<Item = T>) -> Self {
+        Matrix {
+            rows: values.into_iter().map(|x| vec![x]).collect(),
+        }
+    }
+    /// Create a row vector.
+    pub fn row(values: impl IntoIterator<Item = T>) -> Self {
+        Matrix {
+            rows: vec![values.into_iter().collect()],
+        }
+    }
+}
+
+impl<T: DisplayAs<Math>> DisplayAs<Math> for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str(r"\begin{pmatrix}")?;
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                f.write_str(r" \\ ")?;
+            }
+            for (j, x) in row.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(" & ")?;
+                }
+                x.fmt(f)?;
+            }
+        }
+        f.write_str(r"\end{pmatrix}")
+    }
+}
+
+impl<T: DisplayAs<MathML>> DisplayAs<MathML> for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("<mrow><mo>(</mo><mtable>")?;
+        for row in self.rows.iter() {
+            f.write_str("<mtr>")?;
+            for x in row.iter() {
+                f.write_str("<mtd>")?;
+                x.fmt(f)?;
+                f.write_str("</mtd>")?;
+            }
+            f.write_str("</mtr>")?;
+        }
+        f.write_str("</mtable><mo>)</mo></mrow>")
+    }
+}
+
+impl<T: DisplayAs<HTML>> DisplayAs<HTML> for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("<table class=\"matrix\">\n")?;
+        for row in self.rows.iter() {
+            f.write_str("<tr>")?;
+            for x in row.iter() {
+                f.write_str("<td>")?;
+                x.fmt(f)?;
+                f.write_str("</td>")?;
+            }
+            f.write_str("</tr>\n")?;
+        }
+        f.write_str("</table>\n")
+    }
+}
+
+/// In [UTF8] the columns are right-aligned between tall parentheses,
+/// with one line for each row.
+impl<T: DisplayAs<UTF8>> DisplayAs<UTF8> for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|r| {
+                r.iter()
+                    .map(|x| format!("{}", As::<UTF8, T>::from(x)))
+                    .collect()
+            })
+            .collect();
+        let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let mut widths = vec![0; columns];
+        for r in rows.iter() {
+            for (w, x) in widths.iter_mut().zip(r.iter()) {
+                *w = std::cmp::max(*w, x.chars().count());
+            }
+        }
+        for (i, r) in rows.iter().enumerate() {
+            let (open, close) = if rows.len() == 1 {
+                ("(", ")")
+            } else if i == 0 {
+                ("⎛", "⎞")
+            } else if i + 1 == rows.len() {
+                ("⎝", "⎠")
+            } else {
+                ("⎜", "⎟")
+            };
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            f.write_str(open)?;
+            for (j, w) in widths.iter().enumerate() {
+                if j > 0 {
+                    f.write_str("  ")?;
+                }
+                let x = r.get(j).map(|x| x.as_str()).unwrap_or("");
+                write!(f, "{:>w$}", x, w = w)?;
+            }
+            f.write_str(close)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn math() {
+    assert_eq!(
+        &format_as!(Math, Matrix::row(vec![1, 2, 3])).into_string(),
+        r"\begin{pmatrix}1 & 2 & 3\end{pmatrix}"
+    );
+    assert_eq!(
+        &format_as!(Math, Matrix::column(vec!["a_1", "a_2"])).into_string(),
+        r"\begin{pmatrix}a\_1 \\ a\_2\end{pmatrix}"
+    );
+}
+
+#[test]
+fn mathml() {
+    assert_eq!(
+        &format_as!(MathML, Matrix::column(vec![1, 2])).into_string(),
+        "<mrow><mo>(</mo><mtable><mtr><mtd><mn>1</mn></mtd></mtr>\
+         <mtr><mtd><mn>2</mn></mtd></mtr></mtable><mo>)</mo></mrow>"
+    );
+}
+
+#[test]
+fn html() {
+    assert_eq!(
+        &format_as!(HTML, Matrix::new(vec![vec![1.5, 2e10], vec![0.0, -1.0]])).into_string(),
+        "<table class=\"matrix\">\n<tr><td>1.5</td><td>2×10<sup>10</sup></td></tr>\n\
+         <tr><td>0</td><td>-1</td></tr>\n</table>\n"
+    );
+}
+
+#[test]
+fn utf8() {
+    assert_eq!(
+        &format_as!(UTF8, Matrix::row(vec![1, -20])).into_string(),
+        "(1  -20)"
+    );
+    assert_eq!(
+        &format_as!(
+            UTF8,
+            Matrix::new(vec![vec![1, 0, 0], vec![0, 10, 0], vec![0, 0, 100]])
+        )
+        .into_string(),
+        "⎛1   0    0⎞\n⎜0  10    0⎟\n⎝0   0  100⎠"
+    );
+    assert_eq!(
+        &format_as!(UTF8, Matrix::<i32>::new(Vec::<Vec<i32>>::new())).into_string(),
+        ""
+    );
+}
@@ -0,0 +1,182 @@
+//! Choosing a format according to an HTTP `Accept` header.
+
+use super::*;
+
+/// A value that may be displayed in any of several formats, chosen
+/// according to the `Accept` header of an HTTP request.
+///
+/// Each web framework feature makes this a response, with a `Vary:
+/// Accept` header, which is `406 Not Acceptable` if the client
+/// accepts none of the formats.
+///
+/// ```
+/// use display_as::{HTML, Negotiate, UTF8};
+/// let n = Negotiate::new(3e5, Some("text/plain, text/html;q=0.9"))
+///     .format(HTML)
+///     .format(UTF8);
+/// let (mime, body) = n.negotiate(None).unwrap();
+/// assert_eq!(mime.as_ref(), "text/plain; charset=utf-8");
+/// assert_eq!(body, "3e5");
+/// let n = Negotiate::new(3e5, None).format(HTML).format(UTF8);
+/// let (mime, _) = n.negotiate(None).unwrap();
+/// assert_eq!(mime, mime::TEXT_HTML_UTF_8);
+/// assert!(n.negotiate(Some("application/json")).is_none());
+/// ```
+pub struct Negotiate<T> {
+    value: T,
+    formats: Vec<(mime::Mime, Render<T>)>,
+    accept: Option<String>,
+}
+
+/// Displays a value in one format.
+type Render<T> = fn(&T) -> String;
+
+fn render<F: Format, T: DisplayAs<F>>(x: &T) -> String {
+    format!("{}", As::<F, T>::from(x))
+}
+
+impl<T> Negotiate<T> {
+    /// Negotiate the format of `value`, which has no formats yet,
+    /// for a request with this `Accept` header.
+    ///
+    /// The responders of `actix-web`, `gotham` and `rocket` see the
+    /// request, and read its header when `accept` is `None`.  Other
+    /// frameworks cannot, so the handler must pass the header itself,
+    /// as otherwise any format is acceptable.
+    pub fn new(value: T, accept: Option<&str>) -> Self {
+        Negotiate {
+            value,
+            formats: Vec::new(),
+            accept: accept.map(String::from),
+        }
+    }
+    /// Offer format `F`.  Formats offered first are preferred when
+    /// the client likes several equally.
+    pub fn format<F: Format>(mut self, _format: F) -> Self
+    where
+        T: DisplayAs<F>,
+    {
+        self.formats.push((F::mime(), render::<F, T>));
+        self
+    }
+    /// Choose a format for the `Accept` header given to
+    /// [Negotiate::new], or else for `accept`, returning its MIME
+    /// type and the value displayed in it, or `None` if no format is
+    /// acceptable.  Without a header the first format is chosen.
+    pub fn negotiate(&self, accept: Option<&str>) -> Option<(mime::Mime, String)> {
+        let accept = self
+            .accept
+            .as_deref()
+            .or(accept)
+            .filter(|a| !a.trim().is_empty());
+        let mut best = None;
+        let mut best_q = 0.0;
+        for (mime, render) in self.formats.iter() {
+            let q = accept.map_or(1.0, |a| quality(a, mime));
+            if q > best_q {
+                best = Some((mime, render));
+                best_q = q;
+            }
+        }
+        best.map(|(mime, render)| (mime.clone(), render(&self.value)))
+    }
+}
+
+/// The quality the `Accept` header gives `mime`, using the most
+/// specific media range that matches.  A range whose `q` is not a
+/// valid quality between 0 and 1 counts as 0.
+fn quality(accept: &str, mime: &mime::Mime) -> f32 {
+    let mut best: Option<(u8, f32)> = None;
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let (ty, subtype) = match params.next().and_then(|m| m.trim().split_once('/')) {
+            Some(m) => m,
+            None => continue,
+        };
+        let specificity = if ty == "*" && subtype == "*" {
+            0
+        } else if !ty.eq_ignore_ascii_case(mime.type_().as_str()) {
+            continue;
+        } else if subtype == "*" {
+            1
+        } else if subtype.eq_ignore_ascii_case(mime.subtype().as_str()) {
+            2
+        } else {
+            continue;
+        };
+        let q = params
+            .filter_map(|p| p.split_once('='))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case("q"))
+            .map_or(Some(1.0), |(_, v)| parse_quality(v.trim()))
+            .unwrap_or(0.0);
+        match best {
+            Some((s, _)) if s >= specificity => (),
+            _ => best = Some((specificity, q)),
+        }
+    }
+    best.map_or(0.0, |(_, q)| q)
+}
+
+/// Parse a quality value, which RFC 9110 allows to be from `0` to
+/// `1` with at most three decimals.
+fn parse_quality(q: &str) -> Option<f32> {
+    let (whole, decimals) = q.split_once('.').unwrap_or((q, ""));
+    let valid = (whole == "0" || whole == "1")
+        && decimals.len() <= 3
+        && decimals.bytes().all(|b| b.is_ascii_digit());
+    match q.parse() {
+        Ok(q) if valid && q <= 1.0 => Some(q),
+        _ => None,
+    }
+}
+
+#[test]
+fn qualities() {
+    let html = mime::TEXT_HTML_UTF_8;
+    assert_eq!(quality("text/html", &html), 1.0);
+    assert_eq!(quality("TEXT/HTML;level=1", &html), 1.0);
+    assert_eq!(quality("text/*;q=0.5, */*;q=0.1", &html), 0.5);
+    assert_eq!(quality("text/*;q=0.5, text/html;q=0", &html), 0.0);
+    assert_eq!(quality("*/*; q=0.2", &html), 0.2);
+    assert_eq!(quality("image/png, text/plain", &html), 0.0);
+    assert_eq!(quality("garbage", &html), 0.0);
+    assert_eq!(quality("text/html;q=abc", &html), 0.0);
+    assert_eq!(quality("text/html;q=2", &html), 0.0);
+    assert_eq!(quality("text/html;q=1.5", &html), 0.0);
+    assert_eq!(quality("text/html;q=-1", &html), 0.0);
+    assert_eq!(quality("text/html;q=0.0001", &html), 0.0);
+    assert_eq!(quality("text/html;q=NaN", &html), 0.0);
+    assert_eq!(quality("text/html;q=1.000", &html), 1.0);
+    assert_eq!(quality("text/html;q=0.", &html), 0.0);
+}
+
+#[test]
+fn negotiation() {
+    let n = Negotiate::new("a<b", None)
+        .format(HTML)
+        .format(LaTeX)
+        .format(UTF8);
+    let chosen = |accept| n.negotiate(accept).map(|(m, s)| (m.to_string(), s));
+    assert_eq!(
+        chosen(Some("*/*")),
+        Some(("text/html; charset=utf-8".to_string(), "a&lt;b".to_string()))
+    );
+    assert_eq!(
+        chosen(Some("text/x-latex;q=0.8, text/*;q=0.5")),
+        Some(("text/x-latex".to_string(), r"a\textless{}b".to_string()))
+    );
+    assert_eq!(
+        chosen(Some("text/html;q=0, text/*")),
+        Some(("text/x-latex".to_string(), r"a\textless{}b".to_string()))
+    );
+    assert_eq!(chosen(Some("image/*")), None);
+    assert_eq!(
+        chosen(Some("text/html;q=abc, text/x-latex;q=0.5")),
+        Some(("text/x-latex".to_string(), r"a\textless{}b".to_string()))
+    );
+    assert_eq!(n.negotiate(Some("text/html")).unwrap().1, "a&lt;b");
+    let n = Negotiate::new("a<b", Some("text/plain"))
+        .format(HTML)
+        .format(UTF8);
+    assert_eq!(n.negotiate(Some("text/html")).unwrap().1, "a<b");
+}
@@ -0,0 +1,232 @@
+//! [DisplayAs] for numeric types from other crates, each behind a
+//! feature flag named after its crate: `rust_decimal`, `num-bigint`,
+//! `num-rational` and `num-complex`.
+
+use super::*;
+#[allow(unused_imports)]
+use crate::float::Localized;
+
+macro_rules! display_numbers_as {
+    ($format:ty, [$frac:expr, $over:expr, $after_frac:expr],
+     [$before_complex:expr, $plus:expr, $minus:expr, $imaginary:expr]) => {
+        /// A decimal is displayed exactly, with every digit it has,
+        /// and never in scientific notation.
+        #[cfg(feature = "rust_decimal")]
+        impl DisplayAs<$format> for rust_decimal::Decimal {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                crate::float::Floating::from_decimal_str(&self.to_string())
+                    .fmt_positional_as::<$format>(f)
+            }
+        }
+
+        #[cfg(feature = "num-bigint")]
+        impl DisplayAs<$format> for num_bigint::BigInt {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                (self as &dyn Display).fmt(f)
+            }
+        }
+        #[cfg(feature = "num-bigint")]
+        impl DisplayAs<$format> for num_bigint::BigUint {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                (self as &dyn Display).fmt(f)
+            }
+        }
+
+        display_numbers_as!(@fractions $format, [$frac, $over, $after_frac],
+                            [$before_complex, $plus, $minus, $imaginary]);
+    };
+    (@fractions $format:ty, [$frac:expr, $over:expr, $after_frac:expr],
+     [$before_complex:expr, $plus:expr, $minus:expr, $imaginary:expr]) => {
+        #[cfg(feature = "num-rational")]
+        impl<T: DisplayAs<$format>> DisplayAs<$format> for num_rational::Ratio<T> {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                f.write_str($frac)?;
+                <T as DisplayAs<$format>>::fmt(self.numer(), f)?;
+                f.write_str($over)?;
+                <T as DisplayAs<$format>>::fmt(self.denom(), f)?;
+                f.write_str($after_frac)
+            }
+        }
+
+        /// The imaginary part is written after a minus sign only if
+        /// it is less than zero, so a NaN or negative zero imaginary
+        /// part follows a plus sign, as in `1 + NaNi` or `1 + 0i`.
+        #[cfg(feature = "num-complex")]
+        impl<T> DisplayAs<$format> for num_complex::Complex<T>
+        where
+            T: DisplayAs<$format> + Clone + PartialOrd + Default + std::ops::Neg<Output = T>,
+        {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                f.write_str($before_complex)?;
+                <T as DisplayAs<$format>>::fmt(&self.re, f)?;
+                if self.im < T::default() {
+                    f.write_str($minus)?;
+                    <T as DisplayAs<$format>>::fmt(&-self.im.clone(), f)?;
+                } else {
+                    f.write_str($plus)?;
+                    <T as DisplayAs<$format>>::fmt(&self.im, f)?;
+                }
+                f.write_str($imaginary)
+            }
+        }
+    };
+    (@localized $($format:ty),*) => {
+        $(
+            #[cfg(feature = "rust_decimal")]
+            impl DisplayAs<$format> for Localized<rust_decimal::Decimal> {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    crate::float::Floating::from_decimal_str(&self.value.to_string())
+                        .fmt_positional_localized::<$format>(f, self.locale)
+                }
+            }
+            #[cfg(feature = "num-bigint")]
+            impl DisplayAs<$format> for Localized<num_bigint::BigInt> {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    self.locale.fmt_integer::<$format>(f, &self.value)
+                }
+            }
+            #[cfg(feature = "num-bigint")]
+            impl DisplayAs<$format> for Localized<num_bigint::BigUint> {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    self.locale.fmt_integer::<$format>(f, &self.value)
+                }
+            }
+        )*
+    };
+}
+
+display_numbers_as!(CSV, ["", "/", ""], ["", " + ", " - ", "i"]);
+display_numbers_as!(HTML, ["", "/", ""], ["", " + ", " - ", "i"]);
+display_numbers_as!(LaTeX, ["", "/", ""], ["", " + ", " - ", "i"]);
+display_numbers_as!(Markdown, ["", "/", ""], ["", " + ", " - ", "i"]);
+display_numbers_as!(Math, [r"\frac{", "}{", "}"], ["", " + ", " - ", r"\,i"]);
+display_numbers_as!(
+    Rust,
+    ["Ratio::new(", ", ", ")"],
+    ["Complex::new(", ", ", ", -", ")"]
+);
+display_numbers_as!(URL, ["", "/", ""], ["", "%20%2B%20", "%20-%20", "i"]);
+display_numbers_as!(UTF8, ["", "/", ""], ["", " + ", " - ", "i"]);
+display_numbers_as!(@localized crate::csv::Unquoted, HTML, LaTeX, Markdown, Math, UTF8);
+
+/// A decimal is displayed exactly, as for the other formats.
+#[cfg(feature = "rust_decimal")]
+impl DisplayAs<MathML> for rust_decimal::Decimal {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        crate::mathml::write_number(f, &self.to_string())
+    }
+}
+#[cfg(feature = "num-bigint")]
+impl DisplayAs<MathML> for num_bigint::BigInt {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        crate::mathml::write_number(f, &self.to_string())
+    }
+}
+#[cfg(feature = "num-bigint")]
+impl DisplayAs<MathML> for num_bigint::BigUint {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        crate::mathml::write_number(f, &self.to_string())
+    }
+}
+display_numbers_as!(@fractions MathML, ["<mfrac>", "", "</mfrac>"],
+                    ["<mrow>", "<mo>+</mo>", "<mo>−</mo>", "<mi>i</mi></mrow>"]);
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn decimals() {
+    use crate::float::Locale;
+    use rust_decimal::Decimal;
+    let price = Decimal::new(123456750, 2);
+    assert_eq!(&format_as!(HTML, price).into_string(), "1234567.50");
+    assert_eq!(
+        &format_as!(HTML, Localized::new(price, Locale::DE)).into_string(),
+        "1.234.567,50"
+    );
+    assert_eq!(
+        &format_as!(UTF8, Decimal::new(-5, 3)).into_string(),
+        "-0.005"
+    );
+    assert_eq!(&format_as!(UTF8, Decimal::new(0, 2)).into_string(), "0.00");
+    assert_eq!(&format_as!(Rust, price).into_string(), "1234567.50");
+    assert_eq!(&format_as!(URL, price).into_string(), "1234567.50");
+    assert_eq!(
+        &format_as!(MathML, Decimal::new(-5, 3)).into_string(),
+        "<mrow><mo>−</mo><mn>0.005</mn></mrow>"
+    );
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn bigints() {
+    use crate::float::Locale;
+    use num_bigint::BigInt;
+    let x = -BigInt::from(10u64).pow(20u32);
+    assert_eq!(&format_as!(HTML, x).into_string(), "-100000000000000000000");
+    assert_eq!(
+        &format_as!(Math, Localized::new(BigInt::from(1234567), Locale::EN)).into_string(),
+        "1{,}234{,}567"
+    );
+    assert_eq!(
+        &format_as!(CSV, Localized::new(BigInt::from(1234567), Locale::EN)).into_string(),
+        "\"1,234,567\""
+    );
+    assert_eq!(&format_as!(Rust, BigInt::from(42)).into_string(), "42");
+    assert_eq!(&format_as!(URL, BigInt::from(-42)).into_string(), "-42");
+    assert_eq!(
+        &format_as!(MathML, BigInt::from(-42)).into_string(),
+        "<mrow><mo>−</mo><mn>42</mn></mrow>"
+    );
+}
+
+#[cfg(feature = "num-rational")]
+#[test]
+fn ratios() {
+    use num_rational::Ratio;
+    let x = Ratio::new(6, -4);
+    assert_eq!(&format_as!(Math, x).into_string(), r"\frac{-3}{2}");
+    assert_eq!(&format_as!(HTML, x).into_string(), "-3/2");
+    assert_eq!(&format_as!(Rust, x).into_string(), "Ratio::new(-3, 2)");
+    assert_eq!(&format_as!(URL, x).into_string(), "-3/2");
+    assert_eq!(
+        &format_as!(MathML, x).into_string(),
+        "<mfrac><mrow><mo>−</mo><mn>3</mn></mrow><mn>2</mn></mfrac>"
+    );
+}
+
+#[cfg(feature = "num-complex")]
+#[test]
+fn complex() {
+    use num_complex::Complex;
+    assert_eq!(
+        &format_as!(UTF8, Complex::new(1.5, -2.0)).into_string(),
+        "1.5 - 2i"
+    );
+    assert_eq!(
+        &format_as!(Math, Complex::new(1, 2)).into_string(),
+        r"1 + 2\,i"
+    );
+    assert_eq!(
+        &format_as!(HTML, Complex::new(0.0, 3e5)).into_string(),
+        "0 + 3×10<sup>5</sup>i"
+    );
+    assert_eq!(
+        &format_as!(UTF8, Complex::new(1.0, f64::NAN)).into_string(),
+        "1 + NaNi"
+    );
+    assert_eq!(
+        &format_as!(UTF8, Complex::new(1.0, -0.0)).into_string(),
+        "1 + 0i"
+    );
+    assert_eq!(
+        &format_as!(Rust, Complex::new(1.5, -2.0)).into_string(),
+        "Complex::new(1.5, -2)"
+    );
+    assert_eq!(
+        &format_as!(URL, Complex::new(1, 2)).into_string(),
+        "1%20%2B%202i"
+    );
+    assert_eq!(
+        &format_as!(MathML, Complex::new(1.5, -2.0)).into_string(),
+        "<mrow><mn>1.5</mn><mo>−</mo><mn>2</mn><mi>i</mi></mrow>"
+    );
+}
@@ -0,0 +1,202 @@
+//! HTTP responses with a status code, headers and caching.
+
+use super::*;
+
+/// A value displayed as `F`, together with the status code and
+/// headers of an HTTP response.
+///
+/// Each web framework feature makes this a response, just as it does
+/// [`As`], but with the status and headers given here rather than
+/// `200 OK` and only a `Content-Type`.
+///
+/// The format is fixed, so a `Response` cannot be combined with
+/// [`Negotiate`]: a negotiated response is always `200 OK` or `406 Not
+/// Acceptable`, with no headers but `Content-Type` and `Vary`.
+///
+/// ```
+/// use display_as::{display, Response, HTML};
+/// let r = Response::from(display(HTML, &"a<b"))
+///     .status(201)
+///     .header("X-Frame-Options", "DENY")
+///     .cache_control("max-age=3600")
+///     .etag();
+/// let (status, headers, body) = r.into_parts();
+/// assert_eq!(status, 201);
+/// assert_eq!(body, "a&lt;b");
+/// assert_eq!(headers[0], ("Content-Type".to_string(), "text/html; charset=utf-8".to_string()));
+/// assert_eq!(headers[1], ("Cache-Control".to_string(), "max-age=3600".to_string()));
+/// assert_eq!(headers[2].0, "ETag");
+/// assert_eq!(headers[3], ("X-Frame-Options".to_string(), "DENY".to_string()));
+/// ```
+pub struct Response<F: Format, T: DisplayAs<F>> {
+    value: T,
+    _format: F,
+    status: u16,
+    headers: Vec<(String, String)>,
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    etag: bool,
+}
+
+impl<F: Format, T: DisplayAs<F>> Response<F, T> {
+    /// Respond with `value` displayed as `format`, with status `200 OK`.
+    pub fn new(format: F, value: T) -> Self {
+        Response {
+            value,
+            _format: format,
+            status: 200,
+            headers: Vec::new(),
+            content_type: None,
+            cache_control: None,
+            etag: false,
+        }
+    }
+    /// Set the status code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `status` is not a three-digit number.
+    pub fn status(mut self, status: u16) -> Self {
+        assert!(
+            (100..1000).contains(&status),
+            "invalid HTTP status code {}",
+            status
+        );
+        self.status = status;
+        self
+    }
+    /// Add a header.  A `Content-Type` or `Cache-Control` header
+    /// replaces the one set by the response itself, while any other
+    /// is added even if there is already one of the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid header name, or `value` is not
+    /// a valid header value.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let (name, value) = (name.into(), value.into());
+        assert!(
+            !name.is_empty() && name.bytes().all(is_token),
+            "invalid HTTP header name {:?}",
+            name
+        );
+        assert!(
+            value
+                .bytes()
+                .all(|b| b == b'\t' || (b >= b' ' && b != 0x7f)),
+            "invalid HTTP header value {:?}",
+            value
+        );
+        if name.eq_ignore_ascii_case("Content-Type") {
+            self.content_type = Some(value);
+        } else if name.eq_ignore_ascii_case("Cache-Control") {
+            self.cache_control = Some(value);
+        } else {
+            self.headers.push((name, value));
+        }
+        self
+    }
+    /// Set the `Cache-Control` header, e.g. to `"no-cache"` or
+    /// `"public, max-age=3600"`.
+    pub fn cache_control(mut self, directives: impl Into<String>) -> Self {
+        self.cache_control = Some(directives.into());
+        self
+    }
+    /// Add a strong `ETag` header, computed from the displayed body.
+    pub fn etag(mut self) -> Self {
+        self.etag = true;
+        self
+    }
+
+    /// Display the value, returning the status code, the headers
+    /// (starting with `Content-Type`) and the body.
+    pub fn into_parts(self) -> (u16, Vec<(String, String)>, String) {
+        let body = format!("{}", As::<F, T>::from(&self.value));
+        let content_type = self
+            .content_type
+            .unwrap_or_else(|| F::mime().as_ref().to_string());
+        let mut headers = vec![("Content-Type".to_string(), content_type)];
+        if let Some(c) = self.cache_control {
+            headers.push(("Cache-Control".to_string(), c));
+        }
+        if self.etag {
+            headers.push(("ETag".to_string(), etag(&body)));
+        }
+        headers.extend(self.headers);
+        (self.status, headers, body)
+    }
+}
+
+impl<'a, F: Format, T: DisplayAs<F> + ?Sized> From<As<'a, F, T>> for Response<F, &'a T> {
+    fn from(x: As<'a, F, T>) -> Self {
+        Response::new(x._format, x.inner)
+    }
+}
+
+/// Whether `b` may appear in a header name.
+fn is_token(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// A strong entity tag for `body`, from its 64-bit FNV-1a hash.
+fn etag(body: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in body.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100_0000_01b3);
+    }
+    format!("\"{:016x}\"", hash)
+}
+
+#[test]
+fn parts() {
+    let (status, headers, body) = Response::new(UTF8, 3e5).into_parts();
+    assert_eq!(status, 200);
+    assert_eq!(
+        headers,
+        vec![(
+            "Content-Type".to_string(),
+            "text/plain; charset=utf-8".to_string()
+        )]
+    );
+    assert_eq!(body, "3e5");
+
+    let tag = |x: &'static str| Response::new(HTML, x).etag().into_parts().1[1].1.clone();
+    assert_eq!(tag(""), "\"cbf29ce484222325\"");
+    assert_eq!(tag("a"), "\"af63dc4c8601ec8c\"");
+    assert_eq!(tag("a<b"), tag("a<b"));
+    assert_ne!(tag("a<b"), tag("a>b"));
+
+    let (_, headers, _) = Response::new(HTML, 1)
+        .cache_control("no-cache")
+        .header("content-type", "application/xhtml+xml")
+        .header("Cache-Control", "no-store")
+        .header("X-A", "1")
+        .header("X-A", "2")
+        .into_parts();
+    let headers: Vec<_> = headers
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    assert_eq!(
+        headers,
+        vec![
+            ("Content-Type", "application/xhtml+xml"),
+            ("Cache-Control", "no-store"),
+            ("X-A", "1"),
+            ("X-A", "2"),
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn bad_header() {
+    Response::new(HTML, 1).header("X-Evil", "a\r\nb");
+}
+
+#[test]
+#[should_panic]
+fn bad_status() {
+    Response::new(HTML, 1).status(42);
+}
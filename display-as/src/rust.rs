@@ -10,7 +10,7 @@ impl Format for Rust {
         (&s as &dyn std::fmt::Debug).fmt(f)
     }
     fn mime() -> mime::Mime {
-        return "text/x-rust".parse().unwrap();
+        "text/x-rust".parse().unwrap()
     }
     fn this_format() -> Self {
         Rust
@@ -18,13 +18,26 @@ impl Format for Rust {
 }
 
 display_integers_as!(Rust);
+display_as_from_display!(Rust, bool);
+impl DisplayAs<Rust> for char {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        (self as &dyn std::fmt::Debug).fmt(f)
+    }
+}
 display_floats_as!(Rust, "e", "", 1, None);
+display_humanized_as!(Rust);
 
 #[test]
 fn escaping() {
     assert_eq!(&format_as!(Rust, ("&")).into_string(), r#""&""#);
 }
 #[test]
+fn bools_and_chars() {
+    assert_eq!(&format_as!(Rust, true).into_string(), "true");
+    assert_eq!(&format_as!(Rust, 'a').into_string(), "'a'");
+    assert_eq!(&format_as!(Rust, '\'').into_string(), r"'\''");
+}
+#[test]
 fn floats() {
     assert_eq!(&format_as!(Rust, 3.0).into_string(), "3");
     assert_eq!(&format_as!(Rust, 3e5).into_string(), "3e5");
@@ -0,0 +1,139 @@
+//! [DisplayAs] for network addresses, paths and OS strings from the
+//! standard library.
+
+use super::*;
+use std::ffi::{OsStr, OsString};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::path::{Path, PathBuf};
+
+macro_rules! display_as_escaped_display {
+    ($($type:ty),*) => {
+        $(
+            impl<F: Format> DisplayAs<F> for $type {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    F::escape(f, &self.to_string())
+                }
+            }
+        )*
+    };
+}
+display_as_escaped_display!(
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6
+);
+
+/// An [OsStr] that is not valid unicode is displayed lossily, with
+/// invalid sequences replaced by `�`.
+impl<F: Format> DisplayAs<F> for OsStr {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        F::escape(f, &self.to_string_lossy())
+    }
+}
+impl<F: Format> DisplayAs<F> for OsString {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        <OsStr as DisplayAs<F>>::fmt(self, f)
+    }
+}
+
+macro_rules! display_paths_as {
+    ($($format:ty),*) => {
+        $(
+            /// A path that is not valid unicode is displayed lossily,
+            /// with invalid sequences replaced by `�`.
+            impl DisplayAs<$format> for Path {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    <$format as Format>::escape(f, &self.to_string_lossy())
+                }
+            }
+        )*
+    };
+}
+display_paths_as!(CSV, HTML, LaTeX, Markdown, Math, MathML, Rust, UTF8);
+
+/// In a [URL], each segment of a path is percent-encoded separately,
+/// as by [PathSegment], so a segment may contain characters such as
+/// `?`, `%`, `&` or `\`.
+impl DisplayAs<URL> for Path {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let path = self.to_string_lossy();
+        for (i, segment) in path
+            .split(|c| c == '/' || std::path::is_separator(c))
+            .enumerate()
+        {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            <PathSegment<&str> as DisplayAs<URL>>::fmt(&PathSegment(segment), f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: Format> DisplayAs<F> for PathBuf
+where
+    Path: DisplayAs<F>,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        <Path as DisplayAs<F>>::fmt(self, f)
+    }
+}
+
+#[test]
+fn addresses() {
+    let ip: IpAddr = "::1".parse().unwrap();
+    assert_eq!(&format_as!(HTML, ip).into_string(), "::1");
+    let socket: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+    assert_eq!(&format_as!(LaTeX, socket).into_string(), "127.0.0.1:8080");
+    let socket: SocketAddrV6 = "[::1]:80".parse().unwrap();
+    assert_eq!(&format_as!(Rust, socket).into_string(), r#""[::1]:80""#);
+}
+
+#[test]
+fn paths() {
+    let path = Path::new("/home/me/50% of <stuff>?/a_b.txt");
+    assert_eq!(
+        &format_as!(HTML, path).into_string(),
+        "&#x2f;home&#x2f;me&#x2f;50% of &lt;stuff&gt;?&#x2f;a_b.txt"
+    );
+    assert_eq!(
+        &format_as!(LaTeX, path.to_path_buf()).into_string(),
+        r"/home/me/50\% of \textless{}stuff\textgreater{}?/a\_b.txt"
+    );
+    assert_eq!(
+        &format_as!(URL, path).into_string(),
+        "/home/me/50%25%20of%20%3Cstuff%3E%3F/a_b.txt"
+    );
+    assert_eq!(
+        &format_as!(URL, Path::new("a&b=c+d;e/x")).into_string(),
+        "a%26b%3Dc%2Bd%3Be/x"
+    );
+    assert_eq!(&format_as!(UTF8, OsStr::new("a<b")).into_string(), "a<b");
+}
+
+#[cfg(unix)]
+#[test]
+fn backslashes() {
+    assert_eq!(
+        &format_as!(URL, Path::new("a/x\\y")).into_string(),
+        "a/x%5Cy"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn lossy_paths() {
+    use std::os::unix::ffi::OsStrExt;
+    let name = OsStr::from_bytes(b"caf\xe9 & bar");
+    assert_eq!(
+        &format_as!(HTML, name).into_string(),
+        "caf\u{FFFD} &amp; bar"
+    );
+    assert_eq!(
+        &format_as!(URL, Path::new(name)).into_string(),
+        "caf%EF%BF%BD%20%26%20bar"
+    );
+}
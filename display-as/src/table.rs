@@ -0,0 +1,410 @@
+//! A table that can be displayed in several formats.
+
+use super::*;
+
+/// Any value that can be displayed in a [`Table`].
+///
+/// This is implemented for every type that implements [`DisplayAs`]
+/// for [`CSV`], [`HTML`], [`LaTeX`], [`Markdown`] and [`UTF8`].
+pub trait Cell:
+    DisplayAs<CSV> + DisplayAs<HTML> + DisplayAs<LaTeX> + DisplayAs<Markdown> + DisplayAs<UTF8>
+{
+}
+impl<
+        T: DisplayAs<CSV>
+            + DisplayAs<HTML>
+            + DisplayAs<LaTeX>
+            + DisplayAs<Markdown>
+            + DisplayAs<UTF8>
+            + ?Sized,
+    > Cell for T
+{
+}
+
+/// The alignment of a column of a [`Table`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Align {
+    /// Left-aligned, which is the default.
+    Left,
+    /// Centered.
+    Center,
+    /// Right-aligned, which is often best for numbers.
+    Right,
+}
+
+/// A table of data, which may be displayed as a `<table>` in
+/// [`HTML`], a `tabular` in [`LaTeX`], a pipe table in [`Markdown`],
+/// an aligned text table in [`UTF8`], or comma-separated values in
+/// [`CSV`].
+///
+/// ```
+/// use display_as::{Align, CSV, HTML, Table, UTF8, format_as};
+/// let mut table = Table::new();
+/// table.header("Name").header("Age").align(Align::Right);
+/// table.row().cell("David").cell(45);
+/// table.row().cell("Miri").cell(2);
+/// assert_eq!(&format_as!(UTF8, table).into_string(),
+///            "Name  | Age\n------|----\nDavid |  45\nMiri  |   2\n");
+/// assert_eq!(&format_as!(HTML, table).into_string(),
+///            "<table>\n<thead>\n<tr><th>Name</th><th style=\"text-align:right\">Age</th></tr>\n</thead>\n<tbody>\n\
+///             <tr><td>David</td><td style=\"text-align:right\">45</td></tr>\n\
+///             <tr><td>Miri</td><td style=\"text-align:right\">2</td></tr>\n</tbody>\n</table>\n");
+/// assert_eq!(&format_as!(CSV, table).into_string(),
+///            "Name,Age\r\nDavid,45\r\nMiri,2\r\n");
+/// ```
+#[derive(Default)]
+pub struct Table<'a> {
+    headers: Vec<Box<dyn Cell + 'a>>,
+    alignments: Vec<Align>,
+    rows: Vec<Vec<Box<dyn Cell + 'a>>>,
+    caption: Option<Box<dyn Cell + 'a>>,
+}
+
+impl<'a> Table<'a> {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Table::default()
+    }
+    /// Add a column with the given header.
+    pub fn header(&mut self, header: impl Cell + 'a) -> &mut Self {
+        self.headers.push(Box::new(header));
+        if self.alignments.len() < self.headers.len() {
+            self.alignments.push(Align::Left);
+        }
+        self
+    }
+    /// Set the alignment of the most recently added column.
+    pub fn align(&mut self, align: Align) -> &mut Self {
+        if let Some(a) = self.alignments.last_mut() {
+            *a = align;
+        }
+        self
+    }
+    /// Set the caption of the table.
+    pub fn caption(&mut self, caption: impl Cell + 'a) -> &mut Self {
+        self.caption = Some(Box::new(caption));
+        self
+    }
+    /// Start a new row.
+    pub fn row(&mut self) -> &mut Self {
+        self.rows.push(Vec::new());
+        self
+    }
+    /// Add a cell to the current row.
+    pub fn cell(&mut self, cell: impl Cell + 'a) -> &mut Self {
+        if self.rows.is_empty() {
+            self.rows.push(Vec::new());
+        }
+        self.rows.last_mut().unwrap().push(Box::new(cell));
+        self
+    }
+    /// Add a row containing each of the given cells.
+    pub fn cells<C: Cell + 'a>(&mut self, cells: impl IntoIterator<Item = C>) -> &mut Self {
+        self.row();
+        for c in cells {
+            self.cell(c);
+        }
+        self
+    }
+
+    fn num_columns(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|r| r.len())
+            .chain(Some(self.headers.len()))
+            .max()
+            .unwrap_or(0)
+    }
+    fn alignment(&self, column: usize) -> Align {
+        self.alignments.get(column).copied().unwrap_or(Align::Left)
+    }
+}
+
+impl<'a> DisplayAs<HTML> for Table<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let cell = |f: &mut Formatter, tag: &str, column: usize, c: &dyn Cell| {
+            f.write_str("<")?;
+            f.write_str(tag)?;
+            match self.alignment(column) {
+                Align::Left => (),
+                Align::Center => f.write_str(" style=\"text-align:center\"")?,
+                Align::Right => f.write_str(" style=\"text-align:right\"")?,
+            }
+            f.write_str(">")?;
+            <dyn Cell as DisplayAs<HTML>>::fmt(c, f)?;
+            f.write_str("</")?;
+            f.write_str(tag)?;
+            f.write_str(">")
+        };
+        f.write_str("<table>\n")?;
+        if let Some(caption) = &self.caption {
+            f.write_str("<caption>")?;
+            <dyn Cell as DisplayAs<HTML>>::fmt(&**caption, f)?;
+            f.write_str("</caption>\n")?;
+        }
+        if !self.headers.is_empty() {
+            f.write_str("<thead>\n<tr>")?;
+            for (i, h) in self.headers.iter().enumerate() {
+                cell(f, "th", i, &**h)?;
+            }
+            f.write_str("</tr>\n</thead>\n")?;
+        }
+        f.write_str("<tbody>\n")?;
+        for row in self.rows.iter() {
+            f.write_str("<tr>")?;
+            for (i, c) in row.iter().enumerate() {
+                cell(f, "td", i, &**c)?;
+            }
+            f.write_str("</tr>\n")?;
+        }
+        f.write_str("</tbody>\n</table>\n")
+    }
+}
+
+impl<'a> DisplayAs<LaTeX> for Table<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let row = |f: &mut Formatter, cells: &[Box<dyn Cell + 'a>]| {
+            for (i, c) in cells.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(" & ")?;
+                }
+                <dyn Cell as DisplayAs<LaTeX>>::fmt(&**c, f)?;
+            }
+            f.write_str(" \\\\\n")
+        };
+        if let Some(caption) = &self.caption {
+            f.write_str("\\begin{table}\n\\centering\n\\caption{")?;
+            <dyn Cell as DisplayAs<LaTeX>>::fmt(&**caption, f)?;
+            f.write_str("}\n")?;
+        }
+        f.write_str("\\begin{tabular}{")?;
+        for i in 0..self.num_columns() {
+            f.write_str(match self.alignment(i) {
+                Align::Left => "l",
+                Align::Center => "c",
+                Align::Right => "r",
+            })?;
+        }
+        f.write_str("}\n")?;
+        if !self.headers.is_empty() {
+            row(f, &self.headers)?;
+            f.write_str("\\hline\n")?;
+        }
+        for r in self.rows.iter() {
+            row(f, r)?;
+        }
+        f.write_str("\\end{tabular}\n")?;
+        if self.caption.is_some() {
+            f.write_str("\\end{table}\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> DisplayAs<Markdown> for Table<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let columns = self.num_columns();
+        let row = |f: &mut Formatter, cells: &[Box<dyn Cell + 'a>]| {
+            f.write_str("|")?;
+            for i in 0..columns {
+                f.write_str(" ")?;
+                if let Some(c) = cells.get(i) {
+                    <dyn Cell as DisplayAs<Markdown>>::fmt(&**c, f)?;
+                }
+                f.write_str(" |")?;
+            }
+            f.write_str("\n")
+        };
+        row(f, &self.headers)?;
+        f.write_str("|")?;
+        for i in 0..columns {
+            f.write_str(match self.alignment(i) {
+                Align::Left => " --- |",
+                Align::Center => " :-: |",
+                Align::Right => " --: |",
+            })?;
+        }
+        f.write_str("\n")?;
+        for r in self.rows.iter() {
+            row(f, r)?;
+        }
+        if let Some(caption) = &self.caption {
+            f.write_str("\nTable: ")?;
+            <dyn Cell as DisplayAs<Markdown>>::fmt(&**caption, f)?;
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> DisplayAs<UTF8> for Table<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let columns = self.num_columns();
+        let render = |cells: &[Box<dyn Cell + 'a>]| -> Vec<String> {
+            (0..columns)
+                .map(|i| match cells.get(i) {
+                    Some(c) => format!("{}", As::<UTF8, dyn Cell>::from(&**c)),
+                    None => String::new(),
+                })
+                .collect()
+        };
+        let headers = if self.headers.is_empty() {
+            None
+        } else {
+            Some(render(&self.headers))
+        };
+        let rows: Vec<_> = self.rows.iter().map(|r| render(r)).collect();
+        let mut widths = vec![0; columns];
+        for r in headers.iter().chain(rows.iter()) {
+            for (w, c) in widths.iter_mut().zip(r.iter()) {
+                *w = std::cmp::max(*w, c.chars().count());
+            }
+        }
+        let row = |f: &mut Formatter, cells: &[String]| {
+            for (i, c) in cells.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(" | ")?;
+                }
+                let padding = widths[i] - c.chars().count();
+                let (before, after) = match self.alignment(i) {
+                    Align::Left => (0, padding),
+                    Align::Center => (padding / 2, padding - padding / 2),
+                    Align::Right => (padding, 0),
+                };
+                let after = if i + 1 == columns { 0 } else { after };
+                write!(
+                    f,
+                    "{:before$}{}{:after$}",
+                    "",
+                    c,
+                    "",
+                    before = before,
+                    after = after
+                )?;
+            }
+            f.write_str("\n")
+        };
+        if let Some(caption) = &self.caption {
+            <dyn Cell as DisplayAs<UTF8>>::fmt(&**caption, f)?;
+            f.write_str("\n")?;
+        }
+        if let Some(headers) = &headers {
+            row(f, headers)?;
+            for (i, w) in widths.iter().enumerate() {
+                if i > 0 {
+                    f.write_str("-|-")?;
+                }
+                write!(f, "{:-<w$}", "", w = w)?;
+            }
+            f.write_str("\n")?;
+        }
+        for r in rows.iter() {
+            row(f, r)?;
+        }
+        Ok(())
+    }
+}
+
+/// In [CSV] the header (if any) and each row are written as one
+/// record, ending with CRLF as in RFC 4180.  A cell that does not
+/// display as a valid field is quoted as a whole, short rows are padded with empty fields, and the caption
+/// and alignments are omitted.
+impl<'a> DisplayAs<CSV> for Table<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let columns = self.num_columns();
+        let record = |f: &mut Formatter, cells: &[Box<dyn Cell + 'a>]| {
+            for i in 0..columns {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                if let Some(c) = cells.get(i) {
+                    let field = format!("{}", As::<CSV, dyn Cell>::from(&**c));
+                    if crate::csv::is_field(&field) {
+                        f.write_str(&field)?;
+                    } else {
+                        crate::csv::write_field(f, &field)?;
+                    }
+                }
+            }
+            f.write_str("\r\n")
+        };
+        if !self.headers.is_empty() {
+            record(f, &self.headers)?;
+        }
+        for r in self.rows.iter() {
+            record(f, r)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn example() -> Table<'static> {
+    let mut table = Table::new();
+    table.caption("Ages & names");
+    table.header("Name").header("Age").align(Align::Right);
+    table.row().cell("David_R").cell(45);
+    table.cells(vec!["Miri", "2"]);
+    table
+}
+
+#[test]
+fn latex() {
+    assert_eq!(
+        &format_as!(LaTeX, example()).into_string(),
+        "\\begin{table}\n\\centering\n\\caption{Ages \\& names}\n\\begin{tabular}{lr}\n\
+         Name & Age \\\\\n\\hline\nDavid\\_R & 45 \\\\\nMiri & 2 \\\\\n\\end{tabular}\n\\end{table}\n"
+    );
+}
+
+#[test]
+fn markdown() {
+    assert_eq!(
+        &format_as!(Markdown, example()).into_string(),
+        "| Name | Age |\n| --- | --: |\n| David\\_R | 45 |\n| Miri | 2 |\n\nTable: Ages \\& names\n"
+    );
+}
+
+#[test]
+fn utf8() {
+    assert_eq!(
+        &format_as!(UTF8, example()).into_string(),
+        "Ages & names\nName    | Age\n--------|----\nDavid_R |  45\nMiri    |   2\n"
+    );
+    let mut table = Table::new();
+    table.cells(vec!["α", "b"]).cells(vec!["ccc"]);
+    assert_eq!(&format_as!(UTF8, table).into_string(), "α   | b\nccc | \n");
+}
+
+#[test]
+fn csv() {
+    use crate::float::{Locale, Localized};
+    assert_eq!(
+        &format_as!(CSV, example()).into_string(),
+        "Name,Age\r\nDavid_R,45\r\nMiri,2\r\n"
+    );
+    let mut table = Table::new();
+    table.header("Quote").header("Value");
+    table.cells(vec!["He said \"hi\"", "1,5"]);
+    table.row().cell("two\nlines");
+    table.row().cell(1.5e10).cell(true);
+    table
+        .row()
+        .cell(Localized::new(1234, Locale::EN))
+        .cell(Raw("a\"b"));
+    assert_eq!(
+        &format_as!(CSV, table).into_string(),
+        "Quote,Value\r\n\"He said \"\"hi\"\"\",\"1,5\"\r\n\"two\nlines\",\r\n1.5e10,true\r\n\"1,234\",\"a\"\"b\"\r\n"
+    );
+}
+
+#[test]
+fn html() {
+    assert_eq!(
+        &format_as!(HTML, example()).into_string(),
+        "<table>\n<caption>Ages &amp; names</caption>\n<thead>\n\
+         <tr><th>Name</th><th style=\"text-align:right\">Age</th></tr>\n</thead>\n<tbody>\n\
+         <tr><td>David_R</td><td style=\"text-align:right\">45</td></tr>\n\
+         <tr><td>Miri</td><td style=\"text-align:right\">2</td></tr>\n</tbody>\n</table>\n"
+    );
+}
@@ -0,0 +1,308 @@
+//! Transliteration of unicode into LaTeX commands
+
+use super::*;
+use std::fmt::Write;
+
+/// Display as [LaTeX] or [Math] using only ASCII, so that the output
+/// works with pdflatex even without `inputenc`.
+///
+/// Accented letters are written with accent commands, Greek letters
+/// with math commands, and a few other common characters such as
+/// dashes and quotes with their LaTeX equivalents.
+///
+/// Transliteration is lossy: any other character that is not ASCII,
+/// such as a CJK character or an emoji, is written as `?`, since
+/// pdflatex could not typeset it anyway.  Display as [LaTeX] without
+/// `AsciiLaTeX` if you need to keep such characters for a unicode
+/// engine like xelatex or lualatex.
+///
+/// ```
+/// use display_as::{AsciiLaTeX, LaTeX, Math, format_as};
+/// let name = AsciiLaTeX("Erdős & Rényi");
+/// assert_eq!(&format_as!(LaTeX, name).into_string(), r#"Erd\H{o}s \& R\'enyi"#);
+/// assert_eq!(&format_as!(LaTeX, AsciiLaTeX("α-helix")).into_string(), r"$\alpha$-helix");
+/// assert_eq!(&format_as!(Math, AsciiLaTeX("2πr")).into_string(), r"2\pi r");
+/// assert_eq!(&format_as!(LaTeX, AsciiLaTeX("東京")).into_string(), "??");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AsciiLaTeX<T>(pub T);
+
+/// The accents, as (the LaTeX text command, the math command, the
+/// accented letters, the unaccented letters).  Accents without a math
+/// command are wrapped in `\text` in math mode.
+const ACCENTS: [(&str, Option<&str>, &str, &str); 13] = [
+    (
+        "'",
+        Some("acute"),
+        "áéíóúýćńśźĺŕÁÉÍÓÚÝĆŃŚŹĹŔ",
+        "aeiouycnszlrAEIOUYCNSZLR",
+    ),
+    ("`", Some("grave"), "àèìòùÀÈÌÒÙ", "aeiouAEIOU"),
+    (
+        "^",
+        Some("hat"),
+        "âêîôûĉĝĥĵŝŵŷÂÊÎÔÛĈĜĤĴŜŴŶ",
+        "aeioucghjswyAEIOUCGHJSWY",
+    ),
+    ("\"", Some("ddot"), "äëïöüÿÄËÏÖÜŸ", "aeiouyAEIOUY"),
+    ("~", Some("tilde"), "ãñõÃÑÕ", "anoANO"),
+    ("v", Some("check"), "čďěňřšťžČĎĚŇŘŠŤŽ", "cdenrstzCDENRSTZ"),
+    ("=", Some("bar"), "āēīōūĀĒĪŌŪ", "aeiouAEIOU"),
+    ("u", Some("breve"), "ăğŭĂĞŬ", "aguAGU"),
+    (".", Some("dot"), "ėġżĖĠŻİ", "egzEGZI"),
+    ("r", Some("mathring"), "åůÅŮ", "auAU"),
+    ("H", None, "őűŐŰ", "ouOU"),
+    ("c", None, "çşţÇŞŢ", "cstCST"),
+    ("k", None, "ąęįųĄĘĮŲ", "aeiuAEIU"),
+];
+
+/// Other characters, as (the character, its LaTeX in text mode, its
+/// LaTeX in math mode).
+const SYMBOLS: [(char, &str, &str); 39] = [
+    ('ß', r"\ss{}", r"\text{\ss}"),
+    ('æ', r"\ae{}", r"\text{\ae}"),
+    ('Æ', r"\AE{}", r"\text{\AE}"),
+    ('œ', r"\oe{}", r"\text{\oe}"),
+    ('Œ', r"\OE{}", r"\text{\OE}"),
+    ('ø', r"\o{}", r"\text{\o}"),
+    ('Ø', r"\O{}", r"\text{\O}"),
+    ('ł', r"\l{}", r"\text{\l}"),
+    ('Ł', r"\L{}", r"\text{\L}"),
+    ('ı', r"\i{}", r"\imath"),
+    ('–', "--", "-"),
+    ('—', "---", "-"),
+    ('−', "$-$", "-"),
+    ('‘', "`", "`"),
+    ('’', "'", "'"),
+    ('“', "``", r"\text{``}"),
+    ('”', "''", "''"),
+    ('…', r"\ldots{}", r"\ldots"),
+    ('€', r"\texteuro{}", r"\text{\texteuro}"),
+    ('£', r"\pounds{}", r"\pounds"),
+    ('§', r"\S{}", r"\S"),
+    ('©', r"\copyright{}", r"\copyright"),
+    ('°', r"\textdegree{}", r"^\circ"),
+    ('×', r"$\times$", r"\times"),
+    ('÷', r"$\div$", r"\div"),
+    ('±', r"$\pm$", r"\pm"),
+    ('·', r"$\cdot$", r"\cdot"),
+    ('µ', r"$\mu$", r"\mu"),
+    ('≤', r"$\leq$", r"\leq"),
+    ('≥', r"$\geq$", r"\geq"),
+    ('≠', r"$\neq$", r"\neq"),
+    ('≈', r"$\approx$", r"\approx"),
+    ('∞', r"$\infty$", r"\infty"),
+    ('→', r"$\rightarrow$", r"\rightarrow"),
+    ('←', r"$\leftarrow$", r"\leftarrow"),
+    ('\u{A0}', "~", "~"),
+    ('\u{2009}', r"\,", r"\,"),
+    ('\u{202F}', r"\,", r"\,"),
+    ('ϵ', r"$\epsilon$", r"\epsilon"),
+];
+
+/// The Greek letters from `Α` to `ω`, with the names of their LaTeX
+/// commands, or the Latin letter that looks the same.
+const GREEK: [&str; 57] = [
+    "A",
+    "B",
+    r"\Gamma",
+    r"\Delta",
+    "E",
+    "Z",
+    "H",
+    r"\Theta",
+    "I",
+    "K",
+    r"\Lambda",
+    "M",
+    "N",
+    r"\Xi",
+    "O",
+    r"\Pi",
+    "P",
+    "",
+    r"\Sigma",
+    "T",
+    r"\Upsilon",
+    r"\Phi",
+    "X",
+    r"\Psi",
+    r"\Omega",
+    "",
+    "",
+    "",
+    "",
+    "",
+    "",
+    "",
+    r"\alpha",
+    r"\beta",
+    r"\gamma",
+    r"\delta",
+    r"\varepsilon",
+    r"\zeta",
+    r"\eta",
+    r"\theta",
+    r"\iota",
+    r"\kappa",
+    r"\lambda",
+    r"\mu",
+    r"\nu",
+    r"\xi",
+    "o",
+    r"\pi",
+    r"\rho",
+    r"\varsigma",
+    r"\sigma",
+    r"\tau",
+    r"\upsilon",
+    r"\varphi",
+    r"\chi",
+    r"\psi",
+    r"\omega",
+];
+
+/// Append `c`, which is not ASCII, to `out`, in text mode if `math`
+/// is false.
+fn write_transliterated(out: &mut String, c: char, math: bool) -> Result<(), Error> {
+    for &(text, math_accent, accented, plain) in ACCENTS.iter() {
+        if let Some(i) = accented.chars().position(|a| a == c) {
+            // An accent above an i or j replaces its dot.
+            let is_above = !"ck".contains(text);
+            let base = match plain.chars().nth(i).unwrap() {
+                'i' if is_above && math => r"\imath",
+                'j' if is_above && math => r"\jmath",
+                'i' if is_above => r"\i",
+                'j' if is_above => r"\j",
+                _ => &plain[i..i + 1],
+            };
+            return match (math, math_accent) {
+                (true, Some(accent)) => write!(out, r"\{}{{{}}}", accent, base),
+                (true, None) => write!(out, r"\text{{\{}{{{}}}}}", text, base),
+                (false, _) if text.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    write!(out, r"\{}{{{}}}", text, base)
+                }
+                (false, _) if base.starts_with('\\') => write!(out, r"\{}{{{}}}", text, base),
+                (false, _) => write!(out, r"\{}{}", text, base),
+            };
+        }
+    }
+    if let Some(&(_, text, in_math)) = SYMBOLS.iter().find(|s| s.0 == c) {
+        out.push_str(if math { in_math } else { text });
+        return Ok(());
+    }
+    let greek = (c as u32)
+        .checked_sub('Α' as u32)
+        .and_then(|i| GREEK.get(i as usize))
+        .filter(|g| !g.is_empty());
+    match greek {
+        Some(g) if !g.starts_with('\\') || math => out.push_str(g),
+        Some(g) => write!(out, "${}$", g)?,
+        None => out.push('?'),
+    }
+    Ok(())
+}
+
+/// Does `latex` end with a command made of letters, such as `\pi`,
+/// which a following letter would become part of?
+fn ends_with_command(latex: &str) -> bool {
+    match latex.rfind('\\') {
+        Some(idx) => {
+            let name = &latex[idx + 1..];
+            !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphabetic())
+        }
+        None => false,
+    }
+}
+
+/// Write `s`, escaping ASCII as `F` and transliterating everything
+/// else.
+fn write_ascii<F: Format>(f: &mut Formatter, mut s: &str, math: bool) -> Result<(), Error> {
+    // Whether the last thing written was a command that needs a
+    // space before any letter that follows it.
+    let mut needs_space = false;
+    let mut transliterated = String::new();
+    while let Some(idx) = s.find(|c: char| !c.is_ascii()) {
+        let (first, rest) = s.split_at(idx);
+        let c = rest.chars().next().unwrap();
+        if !first.is_empty() {
+            if needs_space && first.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                f.write_str(" ")?;
+            }
+            F::escape(f, first)?;
+            needs_space = false;
+        }
+        transliterated.clear();
+        write_transliterated(&mut transliterated, c, math)?;
+        if needs_space && transliterated.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            f.write_str(" ")?;
+        }
+        f.write_str(&transliterated)?;
+        needs_space = ends_with_command(&transliterated);
+        s = &rest[c.len_utf8()..];
+    }
+    if needs_space && s.is_empty() {
+        // We cannot see what follows, so end the command with an
+        // empty group, which unlike a space never shows in the output.
+        f.write_str("{}")?;
+    } else if needs_space && s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        f.write_str(" ")?;
+    }
+    F::escape(f, s)
+}
+
+impl<T: DisplayAs<UTF8>> DisplayAs<LaTeX> for AsciiLaTeX<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let text = format!("{}", As::<UTF8, T>::from(&self.0));
+        write_ascii::<LaTeX>(f, &text, false)
+    }
+}
+
+impl<T: DisplayAs<UTF8>> DisplayAs<Math> for AsciiLaTeX<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let text = format!("{}", As::<UTF8, T>::from(&self.0));
+        write_ascii::<Math>(f, &text, true)
+    }
+}
+
+#[test]
+fn transliteration() {
+    assert_eq!(
+        &format_as!(LaTeX, AsciiLaTeX("Dvořák, Gödel, Çelik, Wałęsa")).into_string(),
+        r#"Dvo\v{r}\'ak, G\"odel, \c{C}elik, Wa\l{}\k{e}sa"#
+    );
+    assert_eq!(
+        &format_as!(Math, AsciiLaTeX("é_ß")).into_string(),
+        r"\acute{e}\_\text{\ss}"
+    );
+    assert_eq!(
+        &format_as!(LaTeX, AsciiLaTeX("ΑΩω–π")).into_string(),
+        r"A$\Omega$$\omega$--$\pi$"
+    );
+    assert_eq!(
+        &format_as!(Math, AsciiLaTeX("Δx ≤ 3σ")).into_string(),
+        r"\Delta x \leq 3\sigma{}"
+    );
+    assert_eq!(
+        &format_as!(Math, AsciiLaTeX("πΑ·x")).into_string(),
+        r"\pi A\cdot x"
+    );
+    assert_eq!(
+        &format_as!(Math, AsciiLaTeX("íĵïį")).into_string(),
+        r"\acute{\imath}\hat{\jmath}\ddot{\imath}\text{\k{i}}"
+    );
+    assert_eq!(
+        &format_as!(LaTeX, AsciiLaTeX("íĵǐį")).into_string(),
+        r"\'{\i}\^{\j}?\k{i}"
+    );
+    assert_eq!(
+        &format_as!(Math, "x = " AsciiLaTeX("π") "r").into_string(),
+        r"x = \pi{}r"
+    );
+    assert_eq!(
+        &format_as!(LaTeX, AsciiLaTeX("ßπ")).into_string(),
+        r"\ss{}$\pi$"
+    );
+    assert_eq!(&format_as!(LaTeX, AsciiLaTeX("日本")).into_string(), "??");
+    assert_eq!(&format_as!(LaTeX, AsciiLaTeX(3.5)).into_string(), "3.5");
+}
@@ -1,18 +1,75 @@
 //! [Format] as URL, with escaping using percent encoding.
 
 use super::*;
-use percent_encoding::{utf8_percent_encode, DEFAULT_ENCODE_SET};
+use percent_encoding::{utf8_percent_encode, EncodeSet, DEFAULT_ENCODE_SET};
+
+/// Characters that [URL] escapes: those that may not appear in a URL
+/// at all, and those that delimit a query string.
+#[derive(Copy, Clone)]
+struct UrlEncodeSet;
+impl EncodeSet for UrlEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        DEFAULT_ENCODE_SET.contains(byte) || b"%&+=".contains(&byte)
+    }
+}
+
+/// Everything but the unreserved characters of RFC 3986.
+#[derive(Copy, Clone)]
+struct ComponentEncodeSet;
+impl EncodeSet for ComponentEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        !(byte.is_ascii_alphanumeric() || b"-._~".contains(&byte))
+    }
+}
+
+/// Everything but the characters that may appear in the host of a URL
+/// (including an IPv6 literal), so that a host cannot add userinfo, a
+/// path, a query or a fragment.
+#[derive(Copy, Clone)]
+struct HostEncodeSet;
+impl EncodeSet for HostEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        !(byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:[]".contains(&byte))
+    }
+}
+
+/// Everything but the characters that
+/// `application/x-www-form-urlencoded` leaves alone.  Spaces are
+/// handled separately.
+#[derive(Copy, Clone)]
+struct FormEncodeSet;
+impl EncodeSet for FormEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        !(byte.is_ascii_alphanumeric() || b"*-._".contains(&byte))
+    }
+}
+
+fn write_form_encoded(f: &mut Formatter, s: &str) -> Result<(), Error> {
+    for (i, part) in s.split(' ').enumerate() {
+        if i > 0 {
+            f.write_str("+")?;
+        }
+        f.write_str(&utf8_percent_encode(part, FormEncodeSet).to_string())?;
+    }
+    Ok(())
+}
 
 /// [Format] as URL.
+///
+/// Escaping leaves letters, digits and ``!$'()*,-./:;@[\]^_|~``
+/// alone, so that you can include a path, but escapes everything
+/// else, including `?`, `#`, `&`, `=`, `+` and `%`, so that you can
+/// safely include a value in a query string.  For more control, see
+/// [PathSegment], [QueryComponent], [FormEncoded] and [QueryString].
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct URL;
 impl Format for URL {
     fn escape(f: &mut Formatter, s: &str) -> Result<(), Error> {
-        f.write_str(&utf8_percent_encode(s, DEFAULT_ENCODE_SET).to_string())
+        f.write_str(&utf8_percent_encode(s, UrlEncodeSet).to_string())
     }
     /// The MIME type for URL is [mime::TEXT_URL_UTF_8].
     fn mime() -> mime::Mime {
-        return "text/x-url".parse().unwrap();
+        "text/x-url".parse().unwrap()
     }
     fn this_format() -> Self {
         URL
@@ -20,17 +77,330 @@ impl Format for URL {
 }
 
 display_integers_as!(URL);
+display_bools_and_chars_as!(URL);
 display_floats_as!(URL, "e", "", 1, None);
+display_humanized_as!(URL);
+
+/// A value displayed as a single segment of the path of a [URL].
+///
+/// Every character other than letters, digits and `-._~` is
+/// percent-encoded, including `/`.
+///
+/// ```
+/// use display_as::{PathSegment, URL, format_as};
+/// assert_eq!(&format_as!(URL, "/users/" PathSegment("a/b c")).into_string(),
+///            "/users/a%2Fb%20c");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PathSegment<T>(pub T);
+
+/// A value displayed as a key or value in the query string of a
+/// [URL], with every character other than letters, digits and `-._~`
+/// percent-encoded.
+///
+/// ```
+/// use display_as::{QueryComponent, URL, format_as};
+/// assert_eq!(&format_as!(URL, "?q=" QueryComponent("1+1=2 & more")).into_string(),
+///            "?q=1%2B1%3D2%20%26%20more");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QueryComponent<T>(pub T);
+
+/// A value displayed using `application/x-www-form-urlencoded`, as a
+/// browser submits a form, with spaces written as `+`.
+///
+/// ```
+/// use display_as::{FormEncoded, URL, format_as};
+/// assert_eq!(&format_as!(URL, "?q=" FormEncoded("1+1=2 & more")).into_string(),
+///            "?q=1%2B1%3D2+%26+more");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FormEncoded<T>(pub T);
+
+impl<T: DisplayAs<UTF8>> DisplayAs<URL> for PathSegment<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let text = format!("{}", As::<UTF8, T>::from(&self.0));
+        f.write_str(&utf8_percent_encode(&text, ComponentEncodeSet).to_string())
+    }
+}
+impl<T: DisplayAs<UTF8>> DisplayAs<URL> for QueryComponent<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let text = format!("{}", As::<UTF8, T>::from(&self.0));
+        f.write_str(&utf8_percent_encode(&text, ComponentEncodeSet).to_string())
+    }
+}
+impl<T: DisplayAs<UTF8>> DisplayAs<URL> for FormEncoded<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write_form_encoded(f, &format!("{}", As::<UTF8, T>::from(&self.0)))
+    }
+}
+
+/// A query string built from key/value pairs, which are
+/// form-encoded as by [FormEncoded].
+///
+/// The leading `?` is not included, and an empty query string is
+/// displayed as nothing at all.
+///
+/// ```
+/// use display_as::{QueryString, URL, format_as};
+/// let mut query = QueryString::new();
+/// query.pair("q", "fish & chips").pair("page", 2);
+/// assert_eq!(&format_as!(URL, "/search?" query).into_string(),
+///            "/search?q=fish+%26+chips&page=2");
+/// ```
+#[derive(Default)]
+pub struct QueryString<'a> {
+    pairs: Vec<(&'a str, Box<dyn DisplayAs<UTF8> + 'a>)>,
+}
+
+impl<'a> QueryString<'a> {
+    /// Create an empty query string.
+    pub fn new() -> Self {
+        QueryString::default()
+    }
+    /// Add a key/value pair.
+    pub fn pair(&mut self, key: &'a str, value: impl DisplayAs<UTF8> + 'a) -> &mut Self {
+        self.pairs.push((key, Box::new(value)));
+        self
+    }
+    /// Is this query string empty?
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+impl<'a> DisplayAs<URL> for QueryString<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        for (i, (key, value)) in self.pairs.iter().enumerate() {
+            if i > 0 {
+                f.write_str("&")?;
+            }
+            write_form_encoded(f, key)?;
+            f.write_str("=")?;
+            write_form_encoded(f, &format!("{}", As::<UTF8, _>::from(&**value)))?;
+        }
+        Ok(())
+    }
+}
+
+/// The schemes that [Url::new] accepts.
+pub const SAFE_SCHEMES: &[&str] = &["http", "https", "mailto", "ftp", "tel"];
+
+/// The error when creating a [Url] with a scheme that is not allowed,
+/// such as `javascript`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnsafeScheme(pub String);
+
+impl Display for UnsafeScheme {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "URL scheme {:?} is not allowed", self.0)
+    }
+}
+
+impl std::error::Error for UnsafeScheme {}
+
+/// A URL built from its parts, each of which is escaped
+/// appropriately.
+///
+/// Only schemes that are explicitly allowed may be used, so a `Url`
+/// can never be a `javascript:` URL unless you ask for one.  In [HTML]
+/// it is also escaped, so it can safely be included in an attribute.
+///
+/// ```
+/// use display_as::{HTML, URL, Url, format_as};
+/// let mut url = Url::new("https").unwrap();
+/// url.host("example.com").segment("search").segment("a/b").query("q", "x & y");
+/// assert_eq!(&format_as!(URL, url).into_string(),
+///            "https://example.com/search/a%2Fb?q=x+%26+y");
+/// assert_eq!(&format_as!(HTML, "<a href=\"" url "\">").into_string(),
+///            "<a href=\"https:&#x2f;&#x2f;example.com&#x2f;search&#x2f;a%2Fb?q=x+%26+y\">");
+/// assert!(Url::new("javascript").is_err());
+/// ```
+#[derive(Default)]
+pub struct Url<'a> {
+    scheme: Option<&'a str>,
+    host: Option<&'a str>,
+    port: Option<u16>,
+    segments: Vec<Box<dyn DisplayAs<UTF8> + 'a>>,
+    query: QueryString<'a>,
+    fragment: Option<Box<dyn DisplayAs<UTF8> + 'a>>,
+}
+
+impl<'a> Url<'a> {
+    /// Create a URL with the given scheme, which must be one of the
+    /// [SAFE_SCHEMES].
+    pub fn new(scheme: &'a str) -> Result<Self, UnsafeScheme> {
+        Url::with_allowed_schemes(scheme, SAFE_SCHEMES)
+    }
+    /// Create a URL with the given scheme, which must be one of
+    /// `allowed`.
+    pub fn with_allowed_schemes(scheme: &'a str, allowed: &[&str]) -> Result<Self, UnsafeScheme> {
+        let is_valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+        if is_valid && allowed.iter().any(|a| a.eq_ignore_ascii_case(scheme)) {
+            Ok(Url {
+                scheme: Some(scheme),
+                ..Url::default()
+            })
+        } else {
+            Err(UnsafeScheme(scheme.to_string()))
+        }
+    }
+    /// Create a relative URL, with neither scheme nor host, whose path
+    /// begins with `/`.
+    pub fn relative() -> Self {
+        Url::default()
+    }
+    /// Set the host.  Any character that may not appear in a host,
+    /// such as `@`, `/`, `?`, `#`, `\` or a space, is percent-encoded,
+    /// so that it cannot change the meaning of the rest of the URL.
+    pub fn host(&mut self, host: &'a str) -> &mut Self {
+        self.host = Some(host);
+        self
+    }
+    /// Set the port.
+    pub fn port(&mut self, port: u16) -> &mut Self {
+        self.port = Some(port);
+        self
+    }
+    /// Add a segment to the path, which is escaped as by [PathSegment].
+    pub fn segment(&mut self, segment: impl DisplayAs<UTF8> + 'a) -> &mut Self {
+        self.segments.push(Box::new(segment));
+        self
+    }
+    /// Add a key/value pair to the query string.
+    pub fn query(&mut self, key: &'a str, value: impl DisplayAs<UTF8> + 'a) -> &mut Self {
+        self.query.pair(key, value);
+        self
+    }
+    /// Set the fragment, which follows the `#`.
+    pub fn fragment(&mut self, fragment: impl DisplayAs<UTF8> + 'a) -> &mut Self {
+        self.fragment = Some(Box::new(fragment));
+        self
+    }
+}
+
+impl<'a> DisplayAs<URL> for Url<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if let Some(scheme) = self.scheme {
+            f.write_str(scheme)?;
+            f.write_str(":")?;
+        }
+        if let Some(host) = self.host {
+            f.write_str("//")?;
+            f.write_str(&utf8_percent_encode(host, HostEncodeSet).to_string())?;
+            if let Some(port) = self.port {
+                write!(f, ":{}", port)?;
+            }
+        }
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 || self.host.is_some() || self.scheme.is_none() {
+                f.write_str("/")?;
+            }
+            PathSegment(&**segment).fmt(f)?;
+        }
+        if !self.query.is_empty() {
+            f.write_str("?")?;
+            self.query.fmt(f)?;
+        }
+        if let Some(fragment) = &self.fragment {
+            f.write_str("#")?;
+            PathSegment(&**fragment).fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> DisplayAs<HTML> for Url<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        HTML::escape(f, &format!("{}", As::<URL, Self>::from(self)))
+    }
+}
 
 #[test]
 fn escaping() {
-    assert_eq!(&format_as!(URL, ("&")).into_string(), "&");
+    assert_eq!(&format_as!(URL, ("&")).into_string(), "%26");
     assert_eq!(
         &format_as!(URL, ("hello &>this is cool")).into_string(),
-        "hello%20&%3Ethis%20is%20cool"
+        "hello%20%26%3Ethis%20is%20cool"
     );
     assert_eq!(
         &format_as!(URL, ("hello &>this is 'cool")).into_string(),
-        "hello%20&%3Ethis%20is%20\'cool"
+        "hello%20%26%3Ethis%20is%20\'cool"
+    );
+    assert_eq!(
+        &format_as!(URL, ("/a?b=1+1#c%")).into_string(),
+        "/a%3Fb%3D1%2B1%23c%25"
+    );
+    let unescaped = r"!$'()*,-./:;@[\]^_|~";
+    assert_eq!(&format_as!(URL, unescaped).into_string(), unescaped);
+}
+#[test]
+fn components() {
+    assert_eq!(
+        &format_as!(URL, PathSegment("über/..")).into_string(),
+        "%C3%BCber%2F.."
+    );
+    assert_eq!(
+        &format_as!(URL, QueryComponent(1.5e9)).into_string(),
+        "1.5e9"
+    );
+    assert_eq!(
+        &format_as!(URL, FormEncoded("a b+c")).into_string(),
+        "a+b%2Bc"
+    );
+    assert_eq!(&format_as!(URL, QueryString::new()).into_string(), "");
+    let mut query = QueryString::new();
+    query.pair("a key", "x=y").pair("b", 3);
+    assert!(!query.is_empty());
+    assert_eq!(&format_as!(URL, query).into_string(), "a+key=x%3Dy&b=3");
+}
+#[test]
+fn urls() {
+    assert_eq!(
+        Url::new("JavaScript").err(),
+        Some(UnsafeScheme("JavaScript".to_string()))
+    );
+    assert!(Url::new("data").is_err());
+    assert!(Url::with_allowed_schemes("java script", &["java script"]).is_err());
+    let mut url = Url::with_allowed_schemes("data", &["data"]).unwrap();
+    url.segment("text/plain,hi");
+    assert_eq!(
+        &format_as!(URL, url).into_string(),
+        "data:text%2Fplain%2Chi"
+    );
+    let mut url = Url::new("mailto").unwrap();
+    url.segment("me@example.com").query("subject", "Hi there");
+    assert_eq!(
+        &format_as!(URL, url).into_string(),
+        "mailto:me%40example.com?subject=Hi+there"
+    );
+    let mut url = Url::relative();
+    url.segment("users").segment(42).fragment("top");
+    assert_eq!(&format_as!(URL, url).into_string(), "/users/42#top");
+    let mut url = Url::new("http").unwrap();
+    url.host("localhost").port(8080);
+    assert_eq!(
+        &format_as!(HTML, url).into_string(),
+        "http:&#x2f;&#x2f;localhost:8080"
+    );
+}
+#[test]
+fn hosts() {
+    let with_host = |host| {
+        let mut url = Url::new("https").unwrap();
+        url.host(host);
+        format_as!(URL, url).into_string()
+    };
+    assert_eq!(
+        &with_host("good.com@evil.com"),
+        "https://good.com%40evil.com"
     );
+    assert_eq!(&with_host("a.com/x"), "https://a.com%2Fx");
+    assert_eq!(&with_host("a.com?x#y"), "https://a.com%3Fx%23y");
+    assert_eq!(&with_host(r"a.com\x y"), "https://a.com%5Cx%20y");
+    assert_eq!(&with_host("[::1]"), "https://[::1]");
+    assert_eq!(&with_host("bücher.de"), "https://b%C3%BCcher.de");
 }
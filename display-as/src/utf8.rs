@@ -12,15 +12,22 @@ impl Format for UTF8 {
         f.write_str(s)
     }
     fn mime() -> mime::Mime {
-        return mime::TEXT_PLAIN_UTF_8;
+        mime::TEXT_PLAIN_UTF_8
     }
     fn this_format() -> Self {
         UTF8
     }
+    fn infinity(f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("∞")
+    }
 }
 
 display_integers_as!(UTF8);
+display_bools_and_chars_as!(UTF8);
 display_floats_as!(UTF8, "e", "", 1, None);
+display_localized_as!(UTF8, "e", "", 1, None);
+display_measurements_as!(UTF8, "e", "", 1, " ± ", " ", "");
+display_humanized_as!(UTF8, " ", "", " ", crate::float::PrefixStyle::Unicode);
 
 #[test]
 fn escaping() {
@@ -34,3 +41,19 @@ fn floats() {
     assert_eq!(&format_as!(UTF8, 3e3).into_string(), "3e3");
     assert_eq!(&format_as!(UTF8, 3e2).into_string(), "300");
 }
+#[test]
+fn measurements() {
+    use crate::float::{Measured, Quantity};
+    assert_eq!(
+        &format_as!(UTF8, Quantity::new(Measured::new(1.2345, 0.04), "m/s")).into_string(),
+        "1.23(4) m/s"
+    );
+}
+#[test]
+fn si_prefixes() {
+    use crate::float::{Engineering, SiPrefix};
+    assert_eq!(&format_as!(UTF8, SiPrefix::new(1.5e3, "m")).into_string(), "1.5 km");
+    assert_eq!(&format_as!(UTF8, SiPrefix::new(-0.02, "A")).into_string(), "-20 mA");
+    assert_eq!(&format_as!(UTF8, SiPrefix::new(2e30, "g")).into_string(), "2e30 g");
+    assert_eq!(&format_as!(UTF8, Engineering::new(0.05)).into_string(), "50e-3");
+}
@@ -10,6 +10,7 @@ fn just_string() {
     );
 }
 #[test]
+#[allow(clippy::identity_op)]
 fn string_and_integer() {
     assert_eq!(
         format_as!(HTML, r"Number " 3 r" is odd").into_string(),
@@ -106,7 +107,7 @@ fn test_let_again() {
     struct Foo(isize);
     #[with_template("Foo " self.0)]
     impl DisplayAs<HTML> for Foo {}
-    let foos = vec![Foo(1), Foo(2)];
+    let foos = [Foo(1), Foo(2)];
     assert_eq!(
         format_as!(HTML,
                           let foo = {
@@ -4,7 +4,7 @@ use display_as::{format_as, HTML};
 
 #[test]
 fn test_let() {
-    let foos = vec!["hello", "world"];
+    let foos = ["hello", "world"];
     assert_eq!(
         format_as!(HTML, let foo = {
         for i in foos.iter() {
@@ -0,0 +1,143 @@
+extern crate display_as;
+
+use display_as::{format_as, with_response_template, with_template, DisplayAs, Format, HTML, UTF8};
+
+struct Page {
+    title: String,
+}
+
+#[with_response_template("<h1>" &self.title "</h1>")]
+impl DisplayAs<HTML> for Page {}
+
+struct Item<'a, T> {
+    name: &'a str,
+    value: T,
+}
+
+#[with_response_template("<li>" self.name ": " self.value "</li>")]
+impl<'a, T: DisplayAs<HTML> + Send> DisplayAs<HTML> for Item<'a, T> where T: Sync {}
+
+struct Qualified(i32);
+
+#[with_response_template("<b>" self.0 "</b>")]
+impl DisplayAs<display_as::HTML> for Qualified {}
+
+#[with_template("qualified " self.0)]
+impl DisplayAs<UTF8> for Qualified {}
+
+struct Generic;
+
+#[with_response_template("generic" "")]
+impl<F: Format> DisplayAs<F> for Generic {}
+
+#[test]
+fn displays() {
+    let page = Page {
+        title: "a & b".to_string(),
+    };
+    assert_eq!(&format_as!(HTML, page).into_string(), "<h1>a &amp; b</h1>");
+    let item = Item {
+        name: "x",
+        value: 3,
+    };
+    assert_eq!(&format_as!(HTML, item).into_string(), "<li>x: 3</li>");
+    assert_eq!(&format_as!(HTML, Qualified(1)).into_string(), "<b>1</b>");
+    assert_eq!(&format_as!(UTF8, Qualified(1)).into_string(), "qualified 1");
+    assert_eq!(&format_as!(HTML, Generic).into_string(), "generic");
+    assert_eq!(&format_as!(UTF8, Generic).into_string(), "generic");
+}
+
+#[cfg(feature = "actix-web")]
+#[actix_web::test]
+async fn actix_response() {
+    use actix_web::body::MessageBody;
+    use actix_web::http::header::CONTENT_TYPE;
+    use actix_web::test::TestRequest;
+    use actix_web::Responder;
+    let req = TestRequest::default().to_http_request();
+    let response = Page {
+        title: "a & b".to_string(),
+    }
+    .respond_to(&req);
+    assert!(response.status().is_success());
+    assert_eq!(
+        response.headers().get(CONTENT_TYPE).unwrap(),
+        mime::TEXT_HTML_UTF_8.as_ref()
+    );
+    let body = response.into_body().try_into_bytes().unwrap();
+    assert_eq!(&body[..], b"<h1>a &amp; b</h1>");
+    let response = Qualified(1).respond_to(&req);
+    assert_eq!(
+        response.headers().get(CONTENT_TYPE).unwrap(),
+        mime::TEXT_HTML_UTF_8.as_ref()
+    );
+}
+
+#[cfg(feature = "rocket")]
+mod rocket_response {
+    use super::{Item, Page};
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+
+    #[rocket::get("/")]
+    fn index() -> Page {
+        Page {
+            title: "a & b".to_string(),
+        }
+    }
+    #[rocket::get("/item")]
+    fn item() -> Item<'static, i32> {
+        Item {
+            name: "x",
+            value: 3,
+        }
+    }
+
+    #[test]
+    fn responds() {
+        let rocket = rocket::build().mount("/", rocket::routes![index, item]);
+        let client = Client::debug(rocket).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        assert_eq!(response.into_string().unwrap(), "<h1>a &amp; b</h1>");
+        let response = client.get("/item").dispatch();
+        assert_eq!(response.into_string().unwrap(), "<li>x: 3</li>");
+    }
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_response() {
+    use axum::http::header::CONTENT_TYPE;
+    use axum::response::IntoResponse;
+    let response = Page {
+        title: "hello".to_string(),
+    }
+    .into_response();
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        mime::TEXT_HTML_UTF_8.as_ref()
+    );
+    let response = Item {
+        name: "x",
+        value: 3,
+    }
+    .into_response();
+    assert!(response.status().is_success());
+}
+
+#[cfg(feature = "hyper1")]
+#[test]
+fn hyper_response() {
+    use bytes::Bytes;
+    use http_body_util::Full;
+    let response: hyper::Response<Full<Bytes>> = Page {
+        title: "hello".to_string(),
+    }
+    .into();
+    assert_eq!(
+        response.headers()[hyper::header::CONTENT_TYPE],
+        mime::TEXT_HTML_UTF_8.as_ref()
+    );
+}